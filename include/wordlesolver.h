@@ -0,0 +1,88 @@
+/*
+ * C interface to the wordlesolver library.
+ *
+ * Declarations for the functions in src/ffi.rs, link with
+ * -lwordlesolver. Strings are NUL terminated UTF-8. Feedback uses 'g'
+ * (green), 'y' (yellow) or 'b' (black) for each letter of a guess: only
+ * colors feedback is supported, not the count or pegs feedback modes.
+ *
+ * Keep in sync with src/ffi.rs: tests/ffi.rs checks every function is
+ * declared here with the same signature.
+ */
+
+#ifndef WORDLESOLVER_H
+#define WORDLESOLVER_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A list of possible words. */
+typedef struct WordleSolver WordleSolver;
+
+/* Suggestions for the next guess. */
+typedef struct WordleSuggestions WordleSuggestions;
+
+/* Creates a solver from an array of count words. Returns NULL if a word
+ * is NULL or not valid UTF-8. */
+WordleSolver *wordle_solver_new(const char *const *words, size_t count);
+
+/* Frees a solver. Does nothing if solver is NULL. */
+void wordle_solver_free(WordleSolver *solver);
+
+/* Sets whether guesses have to follow the hard mode rules. */
+void wordle_solver_set_hard_mode(WordleSolver *solver, bool hard_mode);
+
+/* Applies a guess and its feedback. Returns 0 on success, or -1 if the
+ * feedback is not valid or the guess does not follow the hard mode
+ * rules. */
+int wordle_solver_apply_guess(WordleSolver *solver, const char *guess, const char *feedback);
+
+/* Removes the last guess. Returns 0 on success, or -1 if there are no
+ * guesses. */
+int wordle_solver_undo(WordleSolver *solver);
+
+/* Removes all the guesses. */
+void wordle_solver_reset(WordleSolver *solver);
+
+/* Returns the number of possible words. */
+size_t wordle_solver_candidate_count(const WordleSolver *solver);
+
+/* Returns the possible word at index, or NULL if index is out of range.
+ * The string is valid until the next guess, undo or reset. */
+const char *wordle_solver_candidate(const WordleSolver *solver, size_t index);
+
+/* Returns up to count suggestions for the next guess, best first. Free
+ * with wordle_suggestions_free(). */
+WordleSuggestions *wordle_solver_suggest(const WordleSolver *solver, size_t count);
+
+/* Returns the number of suggestions. */
+size_t wordle_suggestions_count(const WordleSuggestions *suggestions);
+
+/* Returns the suggested word at index, or NULL if index is out of
+ * range. The string is valid until the suggestions are freed. */
+const char *wordle_suggestions_word(const WordleSuggestions *suggestions, size_t index);
+
+/* Returns the expected information, in bits, of the suggestion at
+ * index. */
+double wordle_suggestions_information(const WordleSuggestions *suggestions, size_t index);
+
+/* Returns the most possible words that can be left after the
+ * suggestion at index. */
+size_t wordle_suggestions_worst_case(const WordleSuggestions *suggestions, size_t index);
+
+/* Returns true if the suggestion at index is one of the possible
+ * words. */
+bool wordle_suggestions_possible(const WordleSuggestions *suggestions, size_t index);
+
+/* Frees suggestions. Does nothing if suggestions is NULL. */
+void wordle_suggestions_free(WordleSuggestions *suggestions);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* WORDLESOLVER_H */
@@ -0,0 +1,289 @@
+// Absurdle: an adversarial host that never commits to an answer. After
+// each guess it gives the feedback that keeps the most possible words, so
+// a game is only won by guessing the last word left.
+
+use std::collections::HashSet;
+
+use crate::wordle::{self, WordleWords};
+
+/// An Absurdle style host, keeping every word that fits the feedback it
+/// has given so far.
+///
+/// # Example
+/// ```
+/// use wordlesolver::absurdle::Absurdle;
+/// use wordlesolver::wordle::WordleWords;
+///
+/// let v = vec![
+///     String::from("bakes"),
+///     String::from("cakes"),
+///     String::from("makes"),
+/// ];
+/// let mut host = Absurdle::new(WordleWords::new(v));
+/// // "bgggg" keeps two words, "ggggg" only one
+/// assert_eq!(host.guess("bakes").unwrap(), "bgggg");
+/// assert_eq!(host.get_word_list().len(), 2);
+/// assert!(!host.is_won());
+/// ```
+pub struct Absurdle {
+    words: WordleWords,
+}
+
+impl Absurdle {
+    /// Returns a host for the possible words of words.
+    pub fn new(words: WordleWords) -> Absurdle {
+        Absurdle { words }
+    }
+
+    /// Returns the feedback to the guess, the one that keeps the most
+    /// possible words, and keeps only those words.
+    ///
+    /// Returns an error if there are no possible words, the guess does
+    /// not have the same number of letters as the words, or it has too
+    /// many letters (more than 12) to group the feedbacks of.
+    pub fn guess(&mut self, guess: &str) -> Result<String, String> {
+        let candidates = self.words.get_word_list();
+        match candidates.first() {
+            None => return Err(String::from("there are no possible words")),
+            Some(word) if word.len() != guess.len() => {
+                return Err(format!("{} does not have {} letters", guess, word.len()))
+            }
+            _ => (),
+        }
+
+        let (index, _) = host_choice(candidates, guess.as_bytes())
+            .ok_or_else(|| format!("{} has too many letters", guess))?;
+        let feedback = feedback_string(index, guess.len());
+        self.words.apply_guess(guess, &feedback)?;
+        Ok(feedback)
+    }
+
+    /// Returns the words that fit every feedback given so far.
+    pub fn get_word_list(&self) -> &Vec<String> {
+        self.words.get_word_list()
+    }
+
+    /// Returns the guesses, and their feedback, so far.
+    pub fn get_guesses(&self) -> &Vec<(String, String)> {
+        self.words.get_guesses()
+    }
+
+    /// Returns true once the host had to give all green feedback.
+    pub fn is_won(&self) -> bool {
+        self.words
+            .get_guesses()
+            .last()
+            .is_some_and(|(_, feedback)| feedback.chars().all(|c| c == 'g'))
+    }
+}
+
+/// Searches for the fewest guesses that force a win against the host,
+/// starting from the possible words of words and guessing from its
+/// original list, leaving out banned words.
+///
+/// At each step only the width guesses that leave the fewest words are
+/// tried, which is much faster than trying every guess but can miss a
+/// shorter win. Returns None if no win in max_guesses is found.
+///
+/// # Example
+/// ```
+/// use wordlesolver::absurdle::solve;
+/// use wordlesolver::wordle::WordleWords;
+///
+/// let v = vec![
+///     String::from("bakes"),
+///     String::from("cakes"),
+///     String::from("makes"),
+/// ];
+/// let words = WordleWords::new(v);
+/// // every guess leaves two words, then one
+/// assert_eq!(solve(&words, 5, 6).unwrap().len(), 3);
+/// assert_eq!(solve(&words, 5, 2), None);
+/// ```
+pub fn solve(words: &WordleWords, width: usize, max_guesses: usize) -> Option<Vec<String>> {
+    let guesses: Vec<String> = words
+        .get_original_list()
+        .iter()
+        .filter(|word| !words.is_banned(word))
+        .cloned()
+        .collect();
+    let candidates = words.get_word_list();
+    // a deeper search only starts once no shorter win was found
+    for depth in 1..=max_guesses {
+        if let Some(path) = search(&guesses, candidates, depth, width) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+// Returns guesses that force a win in at most depth guesses, if there are
+// any among the width best guesses at each step.
+fn search(
+    guesses: &[String],
+    candidates: &[String],
+    depth: usize,
+    width: usize,
+) -> Option<Vec<String>> {
+    if depth == 0 || candidates.is_empty() {
+        return None;
+    }
+    if candidates.len() == 1 {
+        return Some(vec![candidates[0].clone()]);
+    }
+    // with more than one word left, the host never gives all green
+    // feedback, so at least two more guesses are needed
+    if depth == 1 {
+        return None;
+    }
+
+    // the guesses leaving the fewest words first, then possible words
+    let possible: HashSet<&str> = candidates.iter().map(|w| w.as_str()).collect();
+    let mut options: Vec<(usize, bool, &String, usize)> = guesses
+        .iter()
+        .filter_map(|guess| {
+            let (index, left) = host_choice(candidates, guess.as_bytes())?;
+            Some((left, !possible.contains(guess.as_str()), guess, index))
+        })
+        .filter(|&(left, _, _, _)| left < candidates.len())
+        .collect();
+    options.sort();
+
+    for (_, _, guess, index) in options.into_iter().take(width) {
+        let left = host_group(candidates, guess, index);
+        if let Some(mut path) = search(guesses, &left, depth - 1, width) {
+            path.insert(0, guess.clone());
+            return Some(path);
+        }
+    }
+    None
+}
+
+// Returns the feedback, as a feedback_index(), the host gives to the
+// guess, and the number of words it keeps: the feedback that keeps the
+// most words, and on a tie the one with the fewest green, then yellow,
+// letters. Returns None if the guess is too long to group the feedbacks
+// of.
+fn host_choice(candidates: &[String], guess: &[u8]) -> Option<(usize, usize)> {
+    let mut counts = vec![0usize; wordle::feedback_count(guess.len())?];
+    let mut feedback = vec![b'b'; guess.len()];
+    for word in candidates {
+        wordle::score(guess, word.as_bytes(), &mut feedback);
+        counts[wordle::feedback_index(&feedback)] += 1;
+    }
+
+    let mut best = 0;
+    for index in 1..counts.len() {
+        if counts[index] == 0 {
+            continue;
+        }
+        let (greens, yellows) = colors(index, guess.len());
+        let (best_greens, best_yellows) = colors(best, guess.len());
+        if (counts[index], best_greens, best_yellows) > (counts[best], greens, yellows) {
+            best = index;
+        }
+    }
+    Some((best, counts[best]))
+}
+
+// Returns the words that get the feedback, as a feedback_index(), to the
+// guess.
+fn host_group(candidates: &[String], guess: &str, index: usize) -> Vec<String> {
+    let mut feedback = vec![b'b'; guess.len()];
+    candidates
+        .iter()
+        .filter(|word| {
+            wordle::score(guess.as_bytes(), word.as_bytes(), &mut feedback);
+            wordle::feedback_index(&feedback) == index
+        })
+        .cloned()
+        .collect()
+}
+
+// Returns the number of green and yellow letters of a feedback_index().
+fn colors(index: usize, length: usize) -> (usize, usize) {
+    let mut greens = 0;
+    let mut yellows = 0;
+    let mut index = index;
+    for _ in 0..length {
+        match index % 3 {
+            2 => greens += 1,
+            1 => yellows += 1,
+            _ => (),
+        }
+        index /= 3;
+    }
+    (greens, yellows)
+}
+
+// Returns the feedback of a feedback_index() as 'g', 'y' and 'b' letters.
+fn feedback_string(index: usize, length: usize) -> String {
+    let mut letters = Vec::new();
+    let mut index = index;
+    for _ in 0..length {
+        letters.push(match index % 3 {
+            2 => 'g',
+            1 => 'y',
+            _ => 'b',
+        });
+        index /= 3;
+    }
+    letters.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_test_1() {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("makes"),
+            String::from("bcmxx"),
+        ];
+        let mut words = WordleWords::new(v);
+        words.correct_letters(".akes").unwrap();
+        let mut host = Absurdle::new(words);
+        // every word gives a different feedback, so the host picks the
+        // one with the fewest colored letters
+        assert_eq!(host.guess("bcmxx").unwrap(), "bbybb");
+        assert_eq!(host.get_word_list(), &vec![String::from("makes")]);
+        assert_eq!(host.guess("makes").unwrap(), "ggggg");
+        assert!(host.is_won());
+        assert_eq!(host.get_guesses().len(), 2);
+
+        assert!(host.guess("bake").is_err());
+    }
+
+    #[test]
+    fn host_test_2() {
+        let v = vec![String::from("abcdefghijklmn"), String::from("abcdefghijklmo")];
+        let mut host = Absurdle::new(WordleWords::new(v));
+        assert!(host.guess("abcdefghijklmn").is_err());
+    }
+
+    #[test]
+    fn solve_test_1() {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("makes"),
+            String::from("bcmxx"),
+        ];
+        let mut words = WordleWords::new(v);
+        words.correct_letters(".akes").unwrap();
+        assert_eq!(
+            solve(&words, 3, 6),
+            Some(vec![String::from("bcmxx"), String::from("makes")])
+        );
+    }
+
+    #[test]
+    fn feedback_string_test_1() {
+        let index = wordle::feedback_index(b"gybbg");
+        assert_eq!(feedback_string(index, 5), "gybbg");
+        assert_eq!(colors(index, 5), (2, 1));
+    }
+}
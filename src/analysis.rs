@@ -0,0 +1,497 @@
+// Analysis of a finished game: how good each guess was compared with the
+// best guess available at the time, and how lucky its feedback was. Games
+// from a log can be analyzed together, giving a summary for each player.
+
+use serde::Serialize;
+
+use std::collections::HashMap;
+
+use crate::wordle::{Suggestion, WordleWords};
+
+/// How one guess of a game narrowed down the possible words.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepAnalysis {
+    /// the guess
+    pub guess: String,
+    /// the feedback to the guess
+    pub feedback: String,
+    /// number of possible words before the guess
+    pub candidates_before: usize,
+    /// number of possible words after the feedback
+    pub candidates_after: usize,
+    /// information, in bits, the guess was expected to give
+    pub expected_information: f64,
+    /// information, in bits, the feedback actually gave
+    pub actual_information: f64,
+    /// the guess expected to give the most information
+    pub best_guess: String,
+    /// information, in bits, the best guess was expected to give
+    pub best_information: f64,
+    /// information gained above (or below, when negative) what the guess
+    /// was expected to give
+    pub luck: f64,
+    /// true if the guess follows the hard mode rules
+    pub hard_mode: bool,
+}
+
+/// A game from a log of games.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    /// who played the game
+    pub player: String,
+    /// when the game was played, as written in the log
+    pub date: String,
+    /// the guesses and their feedback
+    pub guesses: Vec<(String, String)>,
+}
+
+/// Summary of the games of a player.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerSummary {
+    /// the player
+    pub player: String,
+    /// number of games analyzed
+    pub games: usize,
+    /// number of games ending with the answer
+    pub solved: usize,
+    /// average number of guesses of the solved games
+    pub average_guesses: f64,
+    /// average bits of information per game lost by not playing the best
+    /// guesses (0 is the best)
+    pub average_skill: f64,
+    /// average bits of information per game gained above what was expected
+    pub average_luck: f64,
+    /// the first guess played most often
+    pub favorite_opener: String,
+    /// number of games starting with the favorite opener
+    pub opener_games: usize,
+    /// fraction of games where every guess followed the hard mode rules
+    pub hard_mode_compliance: f64,
+}
+
+/// Analyzes the guesses, and their feedback, of a game played with the
+/// list of words. The filters already applied to words are kept.
+///
+/// Returns an error if a feedback is not valid for its guess, if a guess
+/// does not follow the hard mode rules when words is in hard mode, or if
+/// a feedback leaves no possible words.
+///
+/// # Example
+/// ```
+/// use wordlesolver::analysis::analyze;
+/// use wordlesolver::wordle::WordleWords;
+///
+/// let v = vec![
+///     String::from("react"),
+///     String::from("trace"),
+///     String::from("crane"),
+///     String::from("boxes"),
+/// ];
+/// let words = WordleWords::new(v);
+/// let guesses = vec![(String::from("crane"), String::from("yygby"))];
+/// let steps = analyze(&words, &guesses).unwrap();
+/// assert_eq!(steps[0].candidates_before, 4);
+/// assert_eq!(steps[0].candidates_after, 1);
+/// assert_eq!(steps[0].actual_information, 2.0);
+/// ```
+pub fn analyze(
+    words: &WordleWords,
+    guesses: &[(String, String)],
+) -> Result<Vec<StepAnalysis>, String> {
+    analyze_steps(words, guesses, None)
+}
+
+// Analyzes the guesses of a game. The best first guess is the same for
+// every game, so it can be passed in rather than worked out again.
+fn analyze_steps(
+    words: &WordleWords,
+    guesses: &[(String, String)],
+    best_first: Option<&Suggestion>,
+) -> Result<Vec<StepAnalysis>, String> {
+    let mut words = words.clone();
+    let mut steps = Vec::new();
+
+    for (guess, feedback) in guesses {
+        if !words.get_feedback_mode().is_valid(guess, feedback) {
+            return Err(format!("{} is not a valid feedback for {}", feedback, guess));
+        }
+        let hard_mode = words.is_hard_mode_guess(guess);
+        if words.is_hard_mode() && words.get_lies() == 0 && !hard_mode {
+            return Err(format!("{} does not follow the hard mode rules", guess));
+        }
+
+        let candidates_before = words.get_word_list().len();
+        let expected_information = words.information(guess);
+        let best = match best_first {
+            Some(best) if words.get_guesses().is_empty() => Some(best.clone()),
+            _ => words.suggest(1).pop(),
+        };
+        let (best_guess, best_information) = match best {
+            Some(best) => (best.word, best.information),
+            None => (guess.clone(), expected_information),
+        };
+
+        words.apply_guess(guess, feedback)?;
+        let candidates_after = words.get_word_list().len();
+        if candidates_after == 0 {
+            return Err(format!("no possible words are left after {} {}", guess, feedback));
+        }
+        let actual_information = (candidates_before as f64 / candidates_after as f64).log2();
+
+        steps.push(StepAnalysis {
+            guess: guess.clone(),
+            feedback: feedback.clone(),
+            candidates_before,
+            candidates_after,
+            expected_information,
+            actual_information,
+            best_guess,
+            best_information,
+            luck: actual_information - expected_information,
+            hard_mode,
+        });
+    }
+    Ok(steps)
+}
+
+/// Returns a text report of the analysis, one paragraph for each guess.
+pub fn report(steps: &[StepAnalysis]) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for (index, step) in steps.iter().enumerate() {
+        lines.push(format!("{}. {} {}", index + 1, step.guess, step.feedback));
+        lines.push(format!(
+            "   candidates:  {} -> {}",
+            step.candidates_before, step.candidates_after
+        ));
+        lines.push(format!(
+            "   information: {:.2} bits expected, {:.2} bits gained",
+            step.expected_information, step.actual_information
+        ));
+        if step.best_guess == step.guess
+            || step.best_information - step.expected_information < 0.005
+        {
+            lines.push(String::from("   best guess:  this one"));
+        } else {
+            lines.push(format!(
+                "   best guess:  {} ({:.2} bits expected, {:.2} bits more)",
+                step.best_guess,
+                step.best_information,
+                step.best_information - step.expected_information
+            ));
+        }
+        let luck = if step.luck > 0.005 {
+            "lucky"
+        } else if step.luck < -0.005 {
+            "unlucky"
+        } else {
+            "as expected"
+        };
+        lines.push(format!("   luck:        {:+.2} bits ({})", step.luck, luck));
+    }
+
+    let expected: f64 = steps.iter().map(|s| s.expected_information).sum();
+    let best: f64 = steps.iter().map(|s| s.best_information).sum();
+    let luck: f64 = steps.iter().map(|s| s.luck).sum();
+    lines.push(format!(
+        "total: {:.2} bits expected of {:.2} bits for the best guesses, luck {:+.2} bits",
+        expected, best, luck
+    ));
+    lines.join("\n")
+}
+
+/// Reads games from a log, one game per line as
+/// `player,date,guesses`, with the guesses in
+/// "crane:bygbb slate:bbbyg" format. Fields can be quoted as in CSV, with
+/// `""` for a quote, so a player name can have a comma. Blank lines,
+/// lines starting with '#' and a first line that is the
+/// `player,date,guesses` header are skipped.
+///
+/// # Example
+/// ```
+/// use wordlesolver::analysis::read_games;
+///
+/// let games = read_games("player,date,guesses\nsam,2023-01-02,crane:yygby react:ggggg\n").unwrap();
+/// assert_eq!(games[0].player, "sam");
+/// assert_eq!(games[0].guesses.len(), 2);
+///
+/// let games = read_games("\"lee, jo\",2023-01-02,react:ggggg\n").unwrap();
+/// assert_eq!(games[0].player, "lee, jo");
+/// ```
+pub fn read_games(log: &str) -> Result<Vec<Game>, String> {
+    let mut games = Vec::new();
+    for (index, line) in log.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || (index == 0 && line == "player,date,guesses") {
+            continue;
+        }
+        let columns = match csv_fields(line) {
+            Some(columns) if columns.len() == 3 => columns,
+            _ => return Err(format!("invalid game on line {}: {}", index + 1, line)),
+        };
+        let mut guesses = Vec::new();
+        for item in columns[2].split_whitespace() {
+            match item.split_once(':') {
+                Some((guess, feedback)) => guesses.push((guess.to_string(), feedback.to_string())),
+                None => return Err(format!("invalid guess on line {}: {}", index + 1, item)),
+            }
+        }
+        games.push(Game {
+            player: columns[0].to_string(),
+            date: columns[1].to_string(),
+            guesses,
+        });
+    }
+    Ok(games)
+}
+
+/// Analyzes the games played with the list of words, and returns a
+/// summary for each player, in the order they first appear. Games that
+/// cannot be analyzed are left out, and returned as errors.
+pub fn summarize(words: &WordleWords, games: &[Game]) -> (Vec<PlayerSummary>, Vec<String>) {
+    let best_first = words.suggest(1).pop();
+    let mut players: Vec<String> = Vec::new();
+    let mut analyzed: HashMap<&str, Vec<(&Game, Vec<StepAnalysis>)>> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for game in games {
+        match analyze_steps(words, &game.guesses, best_first.as_ref()) {
+            Ok(steps) if !steps.is_empty() => {
+                if !players.contains(&game.player) {
+                    players.push(game.player.clone());
+                }
+                analyzed.entry(&game.player).or_default().push((game, steps));
+            }
+            Ok(_) => errors.push(format!("{} {}: no guesses", game.player, game.date)),
+            Err(e) => errors.push(format!("{} {}: {}", game.player, game.date, e)),
+        }
+    }
+
+    let summaries = players
+        .iter()
+        .map(|player| summarize_player(player, &analyzed[player.as_str()]))
+        .collect();
+    (summaries, errors)
+}
+
+// summary of the analyzed games of a player
+fn summarize_player(player: &str, games: &[(&Game, Vec<StepAnalysis>)]) -> PlayerSummary {
+    let count = games.len() as f64;
+    let solved: Vec<&Vec<StepAnalysis>> = games
+        .iter()
+        .map(|(_, steps)| steps)
+        .filter(|steps| steps.last().is_some_and(|s| s.feedback.chars().all(|c| c == 'g')))
+        .collect();
+    let average_guesses = if solved.is_empty() {
+        0.0
+    } else {
+        solved.iter().map(|steps| steps.len()).sum::<usize>() as f64 / solved.len() as f64
+    };
+
+    let mut skill = 0.0;
+    let mut luck = 0.0;
+    let mut hard_mode_games = 0;
+    let mut openers: HashMap<&str, usize> = HashMap::new();
+    for (_, steps) in games {
+        skill += steps
+            .iter()
+            .map(|s| s.expected_information - s.best_information)
+            .sum::<f64>();
+        luck += steps.iter().map(|s| s.luck).sum::<f64>();
+        if steps.iter().all(|s| s.hard_mode) {
+            hard_mode_games += 1;
+        }
+        *openers.entry(&steps[0].guess).or_insert(0) += 1;
+    }
+    // the most played opener, the first alphabetically if there is a tie
+    let (favorite_opener, opener_games) = openers
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+        .unwrap_or(("", 0));
+
+    PlayerSummary {
+        player: player.to_string(),
+        games: games.len(),
+        solved: solved.len(),
+        average_guesses,
+        average_skill: skill / count,
+        average_luck: luck / count,
+        favorite_opener: favorite_opener.to_string(),
+        opener_games,
+        hard_mode_compliance: hard_mode_games as f64 / count,
+    }
+}
+
+/// Returns the summaries as CSV, with a header line.
+pub fn summary_csv(summaries: &[PlayerSummary]) -> String {
+    let mut csv = String::from(
+        "player,games,solved,average_guesses,average_skill,average_luck,favorite_opener,opener_games,hard_mode_compliance\n",
+    );
+    for s in summaries {
+        csv.push_str(&format!(
+            "{},{},{},{:.2},{:.2},{:.2},{},{},{:.2}\n",
+            csv_field(&s.player),
+            s.games,
+            s.solved,
+            s.average_guesses,
+            s.average_skill,
+            s.average_luck,
+            csv_field(&s.favorite_opener),
+            s.opener_games,
+            s.hard_mode_compliance
+        ));
+    }
+    csv
+}
+
+// splits a CSV line into its fields, unquoting quoted fields, or returns
+// None if a quote is not closed or is followed by more than spaces
+fn csv_fields(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next()? {
+                    '"' if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    '"' => break,
+                    c => field.push(c),
+                }
+            }
+            while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+            if chars.peek().is_some_and(|c| *c != ',') {
+                return None;
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+            field = field.trim().to_string();
+        }
+        fields.push(field);
+        if chars.next().is_none() {
+            return Some(fields);
+        }
+    }
+}
+
+// quotes a CSV field if it has a comma, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words() -> WordleWords {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("makes"),
+            String::from("bcmxx"),
+        ];
+        WordleWords::new(v)
+    }
+
+    #[test]
+    fn analyze_test_1() {
+        let mut words = words();
+        words.correct_letters(".akes").unwrap();
+        let guesses = vec![
+            (String::from("bakes"), String::from("bgggg")),
+            (String::from("cakes"), String::from("ggggg")),
+        ];
+        let steps = analyze(&words, &guesses).unwrap();
+        assert_eq!(steps.len(), 2);
+
+        // "bcmxx" would have told the three words apart
+        assert_eq!(steps[0].best_guess, "bcmxx");
+        assert!((steps[0].best_information - 3f64.log2()).abs() < 1e-9);
+        // one word in three gives "ggggg", two give "bgggg"
+        let expected = (1.0 / 3.0) * 3f64.log2() + (2.0 / 3.0) * 1.5f64.log2();
+        assert!((steps[0].expected_information - expected).abs() < 1e-9);
+        assert_eq!((steps[0].candidates_before, steps[0].candidates_after), (3, 2));
+        assert!(steps[0].luck < 0.0);
+
+        // either feedback leaves one of the two words
+        assert_eq!(steps[1].candidates_after, 1);
+        assert_eq!(steps[1].luck, 0.0);
+        assert!(report(&steps).contains("best guess:  bcmxx"));
+    }
+
+    #[test]
+    fn analyze_test_2() {
+        let guesses = vec![(String::from("bakes"), String::from("bgg"))];
+        assert!(analyze(&words(), &guesses).is_err());
+        let guesses = vec![(String::from("bakes"), String::from("yyyyy"))];
+        assert!(analyze(&words(), &guesses).is_err());
+
+        // "bcmxx" does not use the green letters of "bakes"
+        let mut words = words();
+        words.set_hard_mode(true);
+        let guesses = vec![
+            (String::from("bakes"), String::from("bgggg")),
+            (String::from("bcmxx"), String::from("bybbb")),
+        ];
+        let result = analyze(&words, &guesses);
+        assert_eq!(result.unwrap_err(), "bcmxx does not follow the hard mode rules");
+    }
+
+    #[test]
+    fn summarize_test_1() {
+        let mut words = words();
+        words.correct_letters(".akes").unwrap();
+        let log = "# games\n\
+            sam,2023-01-01,bakes:bgggg cakes:ggggg\n\
+            sam,2023-01-02,cakes:bgggg bcmxx:gbbbb bakes:ggggg\n\
+            \"kim\",2023-01-01,makes:ggggg\n\
+            kim,2023-01-02,bakes:yyyyy\n";
+        let games = read_games(log).unwrap();
+        assert_eq!(games.len(), 4);
+
+        let (summaries, errors) = summarize(&words, &games);
+        // kim's second game has feedback no word gives
+        assert_eq!(errors.len(), 1);
+        // the quoted "kim" is the same player
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[1].player, "kim");
+
+        let sam = &summaries[0];
+        assert_eq!((sam.games, sam.solved), (2, 2));
+        assert_eq!(sam.average_guesses, 2.5);
+        // a tie, so the first alphabetically
+        assert_eq!((sam.favorite_opener.as_str(), sam.opener_games), ("bakes", 1));
+        // "bcmxx" does not keep the green letters of "cakes"
+        assert_eq!(sam.hard_mode_compliance, 0.5);
+        assert!(sam.average_skill < 0.0);
+
+        let csv = summary_csv(&summaries);
+        assert!(csv.starts_with("player,games,"));
+        assert!(csv.contains("\nkim,1,1,1.00,"));
+    }
+
+    #[test]
+    fn read_games_test_1() {
+        assert!(read_games("sam,2023-01-01\n").is_err());
+        assert!(read_games("sam,2023-01-01,crane\n").is_err());
+        assert!(read_games("\"sam,2023-01-01,crane:ggggg\n").is_err());
+        assert!(read_games("\"sam\"x,2023-01-01,crane:ggggg\n").is_err());
+
+        // only the first line can be the header
+        let games = read_games("player,date,guesses\nplayer,2023-01-01,crane:ggggg\n").unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].player, "player");
+        assert!(read_games("sam,2023-01-01,crane:ggggg\nplayer,date,guesses\n").is_err());
+
+        let games = read_games("\"sam \"\"s\"\"\" , 2023-01-01 ,\"crane:ggggg\"\n").unwrap();
+        assert_eq!(games[0].player, "sam \"s\"");
+        assert_eq!(games[0].date, "2023-01-01");
+        assert_eq!(games[0].guesses.len(), 1);
+    }
+}
@@ -0,0 +1,49 @@
+// JSON answers to requests about a list of possible words, shared by the
+// HTTP server and the stdin/stdout protocol.
+
+use serde_json::{json, Value};
+
+use crate::wordle::WordleWords;
+
+/// Number of suggestions returned when none is asked for.
+pub const DEFAULT_SUGGESTIONS: usize = 5;
+
+/// Applies a guess, returning the number of possible words left.
+pub fn guess(words: &mut WordleWords, guess: &str, feedback: &str) -> Result<Value, String> {
+    words.apply_guess(guess, feedback)?;
+    Ok(json!({ "count": words.get_word_list().len() }))
+}
+
+/// Returns the possible words.
+pub fn candidates(words: &WordleWords) -> Value {
+    let list = words.get_word_list();
+    json!({ "count": list.len(), "candidates": list })
+}
+
+/// Returns up to count suggestions for the next guess.
+pub fn suggestions(words: &WordleWords, count: usize) -> Value {
+    json!({ "suggestions": words.suggest(count) })
+}
+
+/// Removes all the guesses, returning the number of possible words.
+pub fn reset(words: &mut WordleWords) -> Value {
+    words.clear_guesses();
+    json!({ "count": words.get_word_list().len() })
+}
+
+/// Removes the last guess, returning it and the number of possible words.
+pub fn undo(words: &mut WordleWords) -> Result<Value, String> {
+    match words.undo_guess() {
+        Some((guess, feedback)) => Ok(json!({
+            "guess": guess,
+            "feedback": feedback,
+            "count": words.get_word_list().len(),
+        })),
+        None => Err(String::from("no guesses to undo")),
+    }
+}
+
+/// JSON for an error.
+pub fn error(message: &str) -> Value {
+    json!({ "error": message })
+}
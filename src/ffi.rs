@@ -0,0 +1,312 @@
+// C compatible interface to the list of possible words.
+//
+// The functions are declared in include/wordlesolver.h, which has to be
+// kept in sync with this file (tests/ffi.rs checks the header declares
+// every function, with the same signature).
+//
+// Strings are passed as NUL terminated UTF-8. Strings returned to C are
+// owned by the solver, or the suggestions, they came from. Solvers only
+// play with colors feedback, as in Wordle.
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::ptr;
+use std::slice;
+
+use crate::wordle::{self, WordleWords};
+
+/// A list of possible words, created by `wordle_solver_new()` and freed by
+/// `wordle_solver_free()`.
+pub struct WordleSolver {
+    words: WordleWords,
+    // the possible words as C strings, rebuilt whenever the list changes
+    candidates: Vec<CString>,
+}
+
+/// Suggestions for the next guess, created by `wordle_solver_suggest()`
+/// and freed by `wordle_suggestions_free()`.
+pub struct WordleSuggestions {
+    suggestions: Vec<wordle::Suggestion>,
+    // the suggested words as C strings
+    words: Vec<CString>,
+}
+
+impl WordleSolver {
+    fn update_candidates(&mut self) {
+        self.candidates = self
+            .words
+            .get_word_list()
+            .iter()
+            .map(|word| CString::new(word.as_str()).unwrap())
+            .collect();
+    }
+}
+
+// converts a C string to a &str, returning None for NULL or invalid UTF-8
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Creates a solver from an array of count words.
+///
+/// Returns NULL if a word is NULL, is not valid UTF-8 or contains a NUL.
+///
+/// # Safety
+///
+/// `words` has to point to `count` NUL terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_new(
+    words: *const *const c_char,
+    count: usize,
+) -> *mut WordleSolver {
+    let mut list: Vec<String> = Vec::new();
+    if count > 0 {
+        if words.is_null() {
+            return ptr::null_mut();
+        }
+        for &word in slice::from_raw_parts(words, count) {
+            match to_str(word) {
+                Some(word) => list.push(word.to_string()),
+                None => return ptr::null_mut(),
+            }
+        }
+    }
+
+    let mut solver = WordleSolver {
+        words: WordleWords::new(list),
+        candidates: Vec::new(),
+    };
+    solver.update_candidates();
+    Box::into_raw(Box::new(solver))
+}
+
+/// Frees a solver. Does nothing if solver is NULL.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()` and not be used
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_free(solver: *mut WordleSolver) {
+    if !solver.is_null() {
+        drop(Box::from_raw(solver));
+    }
+}
+
+/// Sets whether guesses have to follow the hard mode rules.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_set_hard_mode(solver: *mut WordleSolver, hard_mode: bool) {
+    if let Some(solver) = solver.as_mut() {
+        solver.words.set_hard_mode(hard_mode);
+    }
+}
+
+/// Applies a guess and its feedback ('g', 'y' or 'b' for each letter).
+/// Only colors feedback is supported, not the other feedback modes.
+///
+/// Returns 0 on success, or -1 if the feedback is not valid or the guess
+/// does not follow the hard mode rules.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`, and `guess` and
+/// `feedback` have to be NUL terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_apply_guess(
+    solver: *mut WordleSolver,
+    guess: *const c_char,
+    feedback: *const c_char,
+) -> c_int {
+    let (solver, guess, feedback) = match (solver.as_mut(), to_str(guess), to_str(feedback)) {
+        (Some(solver), Some(guess), Some(feedback)) => (solver, guess, feedback),
+        _ => return -1,
+    };
+    // the solver is always in the colors feedback mode
+    if solver.words.apply_guess(guess, feedback).is_err() {
+        return -1;
+    }
+    solver.update_candidates();
+    0
+}
+
+/// Removes the last guess. Returns 0 on success, or -1 if there are no
+/// guesses.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_undo(solver: *mut WordleSolver) -> c_int {
+    let solver = match solver.as_mut() {
+        Some(solver) => solver,
+        None => return -1,
+    };
+    match solver.words.undo_guess() {
+        Some(_) => {
+            solver.update_candidates();
+            0
+        }
+        None => -1,
+    }
+}
+
+/// Removes all the guesses.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_reset(solver: *mut WordleSolver) {
+    if let Some(solver) = solver.as_mut() {
+        solver.words.clear_guesses();
+        solver.update_candidates();
+    }
+}
+
+/// Returns the number of possible words.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_candidate_count(solver: *const WordleSolver) -> usize {
+    solver.as_ref().map_or(0, |solver| solver.candidates.len())
+}
+
+/// Returns the possible word at index, or NULL if index is out of range.
+///
+/// The string is owned by the solver, and is valid until the next guess,
+/// undo or reset.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_candidate(
+    solver: *const WordleSolver,
+    index: usize,
+) -> *const c_char {
+    solver
+        .as_ref()
+        .and_then(|solver| solver.candidates.get(index))
+        .map_or(ptr::null(), |word| word.as_ptr())
+}
+
+/// Returns up to count suggestions for the next guess, best first.
+///
+/// # Safety
+///
+/// `solver` has to come from `wordle_solver_new()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_solver_suggest(
+    solver: *const WordleSolver,
+    count: usize,
+) -> *mut WordleSuggestions {
+    let solver = match solver.as_ref() {
+        Some(solver) => solver,
+        None => return ptr::null_mut(),
+    };
+    let suggestions = solver.words.suggest(count);
+    let words = suggestions
+        .iter()
+        .map(|s| CString::new(s.word.as_str()).unwrap())
+        .collect();
+    Box::into_raw(Box::new(WordleSuggestions { suggestions, words }))
+}
+
+/// Returns the number of suggestions.
+///
+/// # Safety
+///
+/// `suggestions` has to come from `wordle_solver_suggest()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_suggestions_count(suggestions: *const WordleSuggestions) -> usize {
+    suggestions.as_ref().map_or(0, |s| s.words.len())
+}
+
+/// Returns the suggested word at index, or NULL if index is out of range.
+///
+/// The string is owned by the suggestions.
+///
+/// # Safety
+///
+/// `suggestions` has to come from `wordle_solver_suggest()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_suggestions_word(
+    suggestions: *const WordleSuggestions,
+    index: usize,
+) -> *const c_char {
+    suggestions
+        .as_ref()
+        .and_then(|s| s.words.get(index))
+        .map_or(ptr::null(), |word| word.as_ptr())
+}
+
+/// Returns the expected information, in bits, of the suggestion at index,
+/// or 0 if index is out of range.
+///
+/// # Safety
+///
+/// `suggestions` has to come from `wordle_solver_suggest()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_suggestions_information(
+    suggestions: *const WordleSuggestions,
+    index: usize,
+) -> f64 {
+    suggestions
+        .as_ref()
+        .and_then(|s| s.suggestions.get(index))
+        .map_or(0.0, |s| s.information)
+}
+
+/// Returns the most possible words that can be left after the suggestion
+/// at index, or 0 if index is out of range.
+///
+/// # Safety
+///
+/// `suggestions` has to come from `wordle_solver_suggest()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_suggestions_worst_case(
+    suggestions: *const WordleSuggestions,
+    index: usize,
+) -> usize {
+    suggestions
+        .as_ref()
+        .and_then(|s| s.suggestions.get(index))
+        .map_or(0, |s| s.worst_case)
+}
+
+/// Returns true if the suggestion at index is one of the possible words.
+///
+/// # Safety
+///
+/// `suggestions` has to come from `wordle_solver_suggest()`.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_suggestions_possible(
+    suggestions: *const WordleSuggestions,
+    index: usize,
+) -> bool {
+    suggestions
+        .as_ref()
+        .and_then(|s| s.suggestions.get(index))
+        .is_some_and(|s| s.possible)
+}
+
+/// Frees suggestions. Does nothing if suggestions is NULL.
+///
+/// # Safety
+///
+/// `suggestions` has to come from `wordle_solver_suggest()` and not be
+/// used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wordle_suggestions_free(suggestions: *mut WordleSuggestions) {
+    if !suggestions.is_null() {
+        drop(Box::from_raw(suggestions));
+    }
+}
@@ -0,0 +1,314 @@
+use cursive::{
+    direction::Direction,
+    event::{Callback, Event, EventResult, Key, MouseButton, MouseEvent},
+    theme::{BaseColor, Color, ColorStyle, Effect},
+    view::CannotFocus,
+    Cursive, Printer, Vec2, View,
+};
+
+// width of a tile on screen, including the gap to the next tile
+const TILE_WIDTH: usize = 4;
+
+/// A Wordle style grid of guesses.
+///
+/// Type a guess in a row, then cycle the color of each tile through
+/// gray, yellow and green with the space bar (or by clicking on it).
+/// Enter moves to the next row and calls the `on_submit` callback. The
+/// `on_change` callback is called whenever a letter or a color changes.
+pub struct GuessGrid {
+    // letters typed in each row
+    letters: Vec<Vec<char>>,
+    // feedback ('g', 'y' or 'b') for each letter typed
+    colors: Vec<Vec<char>>,
+    // number of letters in a guess
+    width: usize,
+    // position of the cursor
+    row: usize,
+    col: usize,
+    on_submit: Option<Callback>,
+    on_change: Option<Callback>,
+}
+
+impl GuessGrid {
+    /// Returns an empty grid with rows guesses of width letters.
+    pub fn new(rows: usize, width: usize) -> GuessGrid {
+        GuessGrid {
+            letters: vec![Vec::new(); rows],
+            colors: vec![Vec::new(); rows],
+            width,
+            row: 0,
+            col: 0,
+            on_submit: None,
+            on_change: None,
+        }
+    }
+
+    /// Sets the callback called when Enter is pressed.
+    pub fn on_submit<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Cursive) + 'static,
+    {
+        self.on_submit = Some(Callback::from_fn(f));
+        self
+    }
+
+    /// Sets the callback called when a letter or a color changes.
+    pub fn on_change<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Cursive) + 'static,
+    {
+        self.on_change = Some(Callback::from_fn(f));
+        self
+    }
+
+    /// Returns the guesses, and their feedback, of the rows that have all
+    /// their letters.
+    pub fn get_guesses(&self) -> Vec<(String, String)> {
+        self.letters
+            .iter()
+            .zip(&self.colors)
+            .filter(|(letters, _)| letters.len() == self.width)
+            .map(|(letters, colors)| (letters.iter().collect(), colors.iter().collect()))
+            .collect()
+    }
+
+    /// Fills the grid with guesses and their feedback, clearing the other
+    /// rows.
+    pub fn set_guesses(&mut self, guesses: &[(String, String)]) {
+        for row in 0..self.letters.len() {
+            match guesses.get(row) {
+                Some((guess, feedback)) => {
+                    self.letters[row] = guess.chars().take(self.width).collect();
+                    self.colors[row] = feedback.chars().take(self.width).collect();
+                }
+                None => {
+                    self.letters[row].clear();
+                    self.colors[row].clear();
+                }
+            }
+        }
+        self.row = guesses.len().min(self.letters.len().saturating_sub(1));
+        self.col = self.letters[self.row].len().min(self.width.saturating_sub(1));
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// Changes the number of rows (at least one), adding empty rows at the
+    /// bottom or removing the last rows.
+    pub fn set_rows(&mut self, rows: usize) {
+        let rows = rows.max(1);
+        self.letters.resize(rows, Vec::new());
+        self.colors.resize(rows, Vec::new());
+        self.row = self.row.min(rows - 1);
+        self.col = self.col.min(self.letters[self.row].len());
+    }
+
+    /// Replaces the letters of the current row with word, with every
+    /// tile gray.
+    pub fn fill_row(&mut self, word: &str) {
+        let row = self.row;
+        self.letters[row] = word.chars().take(self.width).collect();
+        self.colors[row] = vec!['b'; self.letters[row].len()];
+        self.col = self.letters[row].len().min(self.width.saturating_sub(1));
+    }
+
+    // the next color for a tile: gray, yellow, green, then gray again
+    fn cycle(&mut self, row: usize, col: usize) {
+        if let Some(color) = self.colors[row].get_mut(col) {
+            *color = match *color {
+                'b' => 'y',
+                'y' => 'g',
+                _ => 'b',
+            };
+        }
+    }
+
+    fn submit(&mut self) -> EventResult {
+        if self.row + 1 < self.letters.len() && self.letters[self.row].len() == self.width {
+            self.row += 1;
+            self.col = 0;
+        }
+        EventResult::Consumed(self.on_submit.clone())
+    }
+}
+
+impl View for GuessGrid {
+    fn draw(&self, printer: &Printer) {
+        for row in 0..self.letters.len() {
+            for col in 0..self.width {
+                let letter = self.letters[row].get(col).copied().unwrap_or(' ');
+                let back = match self.colors[row].get(col) {
+                    Some('g') => Color::Dark(BaseColor::Green),
+                    Some('y') => Color::Dark(BaseColor::Yellow),
+                    Some(_) => Color::Light(BaseColor::Black),
+                    None => Color::Dark(BaseColor::White),
+                };
+                let style = ColorStyle::new(Color::Dark(BaseColor::Black), back);
+                let text = format!(" {} ", letter.to_ascii_uppercase());
+                let position = (col * TILE_WIDTH, row);
+                printer.with_color(style, |printer| {
+                    if printer.focused && row == self.row && col == self.col {
+                        printer.with_effect(Effect::Reverse, |printer| {
+                            printer.print(position, &text)
+                        });
+                    } else {
+                        printer.print(position, &text);
+                    }
+                });
+            }
+        }
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        Vec2::new(self.width * TILE_WIDTH - 1, self.letters.len())
+    }
+
+    fn take_focus(&mut self, _source: Direction) -> Result<EventResult, CannotFocus> {
+        Ok(EventResult::Consumed(None))
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        let row = self.row;
+        match event {
+            Event::Char(' ') => self.cycle(row, self.col),
+            Event::Char(c) if c.is_ascii_alphabetic() => {
+                let c = c.to_ascii_lowercase();
+                if self.col < self.letters[row].len() {
+                    self.letters[row][self.col] = c;
+                } else if self.letters[row].len() < self.width {
+                    self.letters[row].push(c);
+                    self.colors[row].push('b');
+                }
+                self.col = (self.col + 1).min(self.width - 1);
+            }
+            Event::Key(Key::Backspace) => {
+                // remove the last letter of the row
+                self.letters[row].pop();
+                self.colors[row].pop();
+                self.col = self.letters[row].len().min(self.width - 1);
+            }
+            Event::Key(Key::Left) if self.col > 0 => {
+                self.col -= 1;
+                return EventResult::Consumed(None);
+            }
+            Event::Key(Key::Right) if self.col + 1 < self.width => {
+                self.col += 1;
+                return EventResult::Consumed(None);
+            }
+            Event::Key(Key::Up) if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.letters[self.row].len());
+                return EventResult::Consumed(None);
+            }
+            Event::Key(Key::Down) if self.row + 1 < self.letters.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.letters[self.row].len());
+                return EventResult::Consumed(None);
+            }
+            Event::Key(Key::Enter) => return self.submit(),
+            Event::Mouse {
+                offset,
+                position,
+                event: MouseEvent::Press(MouseButton::Left),
+            } => {
+                let position = match position.checked_sub(offset) {
+                    Some(position) => position,
+                    None => return EventResult::Ignored,
+                };
+                let (row, col) = (position.y, position.x / TILE_WIDTH);
+                if row >= self.letters.len() || col >= self.width {
+                    return EventResult::Ignored;
+                }
+                self.row = row;
+                self.col = col.min(self.letters[row].len());
+                self.cycle(row, col);
+            }
+            _ => return EventResult::Ignored,
+        }
+        // a letter or a color changed
+        EventResult::Consumed(self.on_change.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(grid: &mut GuessGrid, text: &str) {
+        for c in text.chars() {
+            grid.on_event(Event::Char(c));
+        }
+    }
+
+    #[test]
+    fn grid_test_1() {
+        let mut grid = GuessGrid::new(6, 5);
+        type_text(&mut grid, "crane");
+        assert_eq!(
+            grid.get_guesses(),
+            vec![(String::from("crane"), String::from("bbbbb"))]
+        );
+
+        // color the 'r' yellow and the 'a' green
+        grid.on_event(Event::Key(Key::Left));
+        grid.on_event(Event::Key(Key::Left));
+        grid.on_event(Event::Char(' '));
+        grid.on_event(Event::Char(' '));
+        grid.on_event(Event::Key(Key::Left));
+        grid.on_event(Event::Char(' '));
+        assert_eq!(
+            grid.get_guesses(),
+            vec![(String::from("crane"), String::from("bygbb"))]
+        );
+
+        // an incomplete row is not a guess
+        grid.on_event(Event::Key(Key::Enter));
+        type_text(&mut grid, "sla");
+        assert_eq!(grid.get_guesses().len(), 1);
+        grid.on_event(Event::Key(Key::Backspace));
+        type_text(&mut grid, "ate");
+        assert_eq!(grid.get_guesses()[1].0, "slate");
+    }
+
+    #[test]
+    fn grid_test_2() {
+        let mut grid = GuessGrid::new(3, 5);
+        let guesses = vec![(String::from("crane"), String::from("bygbb"))];
+        grid.set_guesses(&guesses);
+        assert_eq!(grid.get_guesses(), guesses);
+        grid.set_guesses(&[]);
+        assert!(grid.get_guesses().is_empty());
+    }
+
+    #[test]
+    fn grid_test_3() {
+        let mut grid = GuessGrid::new(3, 5);
+        type_text(&mut grid, "cr");
+        grid.on_event(Event::Char(' '));
+        grid.fill_row("slate");
+        assert_eq!(
+            grid.get_guesses(),
+            vec![(String::from("slate"), String::from("bbbbb"))]
+        );
+    }
+
+    #[test]
+    fn grid_test_4() {
+        let mut grid = GuessGrid::new(2, 5);
+        let guesses = vec![
+            (String::from("crane"), String::from("bygbb")),
+            (String::from("slate"), String::from("bbbbg")),
+        ];
+        grid.set_guesses(&guesses);
+        grid.set_rows(13);
+        assert_eq!(grid.rows(), 13);
+        assert_eq!(grid.get_guesses(), guesses);
+        grid.set_rows(1);
+        assert_eq!(grid.get_guesses(), guesses[..1].to_vec());
+        grid.set_rows(0);
+        assert_eq!(grid.rows(), 1);
+    }
+}
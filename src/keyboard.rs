@@ -0,0 +1,65 @@
+use cursive::{
+    theme::{BaseColor, Color, ColorStyle},
+    Printer, Vec2, View,
+};
+
+use crate::wordle::LetterState;
+
+// rows of keys, as on a QWERTY keyboard
+const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+// width of a key on screen, including the gap to the next key
+const KEY_WIDTH: usize = 4;
+
+/// A keyboard showing what is known about each letter, like the one in
+/// Wordle: unknown letters in white, absent letters in gray, present
+/// letters in yellow and placed letters in green.
+pub struct Keyboard {
+    states: [LetterState; 26],
+}
+
+impl Keyboard {
+    /// Returns a keyboard with every letter unknown.
+    pub fn new() -> Keyboard {
+        Keyboard {
+            states: [LetterState::Unknown; 26],
+        }
+    }
+
+    /// Sets the state of each letter, 'a' to 'z'.
+    pub fn set_states(&mut self, states: [LetterState; 26]) {
+        self.states = states;
+    }
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl View for Keyboard {
+    fn draw(&self, printer: &Printer) {
+        for (y, row) in ROWS.iter().enumerate() {
+            // indent each row by half a key more than the one above
+            let indent = y * KEY_WIDTH / 2;
+            for (x, letter) in row.bytes().enumerate() {
+                let back = match self.states[(letter - b'a') as usize] {
+                    LetterState::Unknown => Color::Dark(BaseColor::White),
+                    LetterState::Absent => Color::Light(BaseColor::Black),
+                    LetterState::Present => Color::Dark(BaseColor::Yellow),
+                    LetterState::Placed => Color::Dark(BaseColor::Green),
+                };
+                let style = ColorStyle::new(Color::Dark(BaseColor::Black), back);
+                let text = format!(" {} ", letter.to_ascii_uppercase() as char);
+                printer.with_color(style, |printer| {
+                    printer.print((indent + x * KEY_WIDTH, y), &text)
+                });
+            }
+        }
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        Vec2::new(ROWS[0].len() * KEY_WIDTH - 1, ROWS.len())
+    }
+}
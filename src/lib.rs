@@ -0,0 +1,10 @@
+// Library to find possible Wordle words, used by the wordlesolver
+// program and, through the ffi module, from C.
+
+pub mod absurdle;
+pub mod analysis;
+pub mod ffi;
+pub mod nerdle;
+pub mod share;
+pub mod wordle;
+pub mod xordle;
@@ -1,16 +1,41 @@
 // Program to display possible Wordle Words
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, prelude::*, BufReader};
 use std::path::PathBuf;
+use std::process;
 
-mod wordle;
-use wordle::WordleWords;
+use wordlesolver::absurdle::{self, Absurdle};
+use wordlesolver::analysis;
+use wordlesolver::nerdle;
+use wordlesolver::share;
+use wordlesolver::xordle::Xordle;
+use wordlesolver::wordle;
+use wordle::{FeedbackMode, WordleWords};
+
+mod api;
+
+mod grid;
+
+mod keyboard;
+
+mod repl;
+
+mod server;
+
+mod session;
+use session::Session;
+
+mod stdio;
 
 mod tui;
 
+mod userlist;
+use userlist::UserList;
+
 // Structure for our command line arguments
 /// Program to display possible Wordle Words
 #[derive(Parser, Debug)]
@@ -24,6 +49,14 @@ struct Args {
     #[arg(short, long)]
     tui: bool,
 
+    /// Use an interactive command line (REPL), starting from the other options
+    #[arg(short, long)]
+    repl: bool,
+
+    /// Answer newline delimited JSON requests on stdin, starting from the other options
+    #[arg(long)]
+    stdio: bool,
+
     /// Exclude words with these letters
     #[arg(short, long)]
     exclude: Option<String>,
@@ -35,6 +68,207 @@ struct Args {
     /// Letters in incorrect positions. '.' for those not yet known. Format in "xxxxx yyyyy zzzzz ....." format
     #[arg(short, long)]
     incorrect: Option<String>,
+
+    /// Guesses and their feedback ('g'reen, 'y'ellow, 'b'lack). Format in "crane:bygbb slate:bbbyg" format
+    #[arg(short, long)]
+    guess: Option<String>,
+
+    /// Crossword style query: a pattern ('?' any letter, [bcd] one of the letters, [^bcd] any other), +letters (contains), -letters (does not contain), =letters (only uses). Format in "?a?e? +r =abcenrst" format
+    #[arg(short, long, allow_hyphen_values = true)]
+    query: Option<String>,
+
+    /// Words that are anagrams of these letters (e.g. "tacer"), when the letters are known but not their order
+    #[arg(long)]
+    anagram: Option<String>,
+
+    /// Words that only use these letters. A letter can be used any number of times, unless --only-once is given
+    #[arg(long)]
+    only: Option<String>,
+
+    /// With --only, use each letter at most as many times as it is given
+    #[arg(long)]
+    only_once: bool,
+
+    /// Guesses have to follow the hard mode rules
+    #[arg(long)]
+    hard: bool,
+
+    /// Number of boards being played (e.g. 2 for Dordle)
+    #[arg(long)]
+    boards: Option<usize>,
+
+    /// Number of tiles of each feedback that can be wrong (e.g. 1 for Fibble). Words are ranked by how many feedbacks they match exactly
+    #[arg(long)]
+    lies: Option<usize>,
+
+    /// How guesses are scored: colors (Wordle), count, the number of letters in common with the answer (Jotto, e.g. "crane:2"), or pegs, the number of black and white pegs (Mastermind, e.g. "1234:1b2w")
+    #[arg(long)]
+    feedback: Option<FeedbackMode>,
+
+    /// Use every code of these symbols (e.g. "123456" for Mastermind) instead of the list of words. Guesses are scored with pegs
+    #[arg(long, conflicts_with = "nerdle")]
+    codes: Option<String>,
+
+    /// Length of the codes
+    #[arg(long, default_value_t = 4, requires = "codes")]
+    length: usize,
+
+    /// Use each symbol at most once in a code (e.g. for Bulls and Cows)
+    #[arg(long, requires = "codes")]
+    no_repeats: bool,
+
+    /// Use every Nerdle equation of this length, from 5 to 8 (e.g. 8, or 6 for Mini Nerdle), instead of the list of words
+    #[arg(long)]
+    nerdle: Option<usize>,
+
+    /// Number of guess rows in the TUI (e.g. 9 for Quordle, 13 for Octordle)
+    #[arg(long, default_value_t = 8)]
+    rows: usize,
+
+    /// Words to ban: they are never possible answers, nor suggested as guesses, e.g. recent answers or words the game rejects. Format in "xxxxx yyyyy" format. Saved in ~/.wordlesolver.words
+    #[arg(long)]
+    ban: Option<String>,
+
+    /// Words to stop banning. Format in "xxxxx yyyyy" format
+    #[arg(long)]
+    unban: Option<String>,
+
+    /// Words to pin: they are always in the list of words. Format in "xxxxx yyyyy" format
+    #[arg(long)]
+    pin: Option<String>,
+
+    /// Words to stop pinning. Format in "xxxxx yyyyy" format
+    #[arg(long)]
+    unpin: Option<String>,
+
+    /// File of past answers, one per line with an optional date (e.g. "2022-06-19 crane"). They are not possible words
+    #[arg(long)]
+    past: Option<PathBuf>,
+
+    /// Keep past answers as possible words, but much less likely ones
+    #[arg(long)]
+    downweight_past: bool,
+
+    /// Session file. It is loaded (if it exists), updated with the other options and saved
+    #[arg(short, long)]
+    session: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Start a local HTTP server answering JSON requests. Sessions use the list, mode and past answer options
+    Serve {
+        /// Port to listen on (on localhost only)
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Rate each guess of a game against the best guess available at the time
+    Analyze {
+        /// Guesses and their feedback ('g'reen, 'y'ellow, 'b'lack). Format in "crane:bygbb slate:bbbyg" format
+        guesses: String,
+
+        /// Print the analysis as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Play against an Absurdle style host that keeps the most words after each guess
+    Absurdle {
+        /// Search for the fewest guesses that force a win instead of playing
+        #[arg(long)]
+        solve: bool,
+
+        /// Number of guesses tried at each step of the search
+        #[arg(long, default_value_t = 10)]
+        width: usize,
+
+        /// Most guesses to search for
+        #[arg(long, default_value_t = 6)]
+        max_guesses: usize,
+    },
+    /// Analyze a log of games, one "player,date,crane:bygbb slate:bbbyg" game per line, and print a CSV summary for each player
+    Batch {
+        /// File with the log of games
+        log: PathBuf,
+    },
+    /// List the guesses that could have given each row of a share grid, for a known answer
+    Reverse {
+        /// The answer of the game
+        answer: String,
+
+        /// The share grid, with a line of squares (or 'g', 'y' and 'b' letters) per row. Read from stdin if not given
+        grid: Option<String>,
+    },
+    /// Rank the possible answers by how many share grids, without the words, they could have given
+    Infer {
+        /// File with the share grids, one after the other. Read from stdin if not given
+        grids: Option<PathBuf>,
+
+        /// Number of answers to show
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// List the pairs of words, sharing no letters, that fit the combined feedback of a Xordle game
+    Xordle {
+        /// Guesses and their combined feedback ('g'reen, 'y'ellow, 'b'lack). Format in "crane:bygbb slate:bbbyg" format
+        guesses: String,
+
+        /// Number of suggestions for the next guess to show after the pairs
+        #[arg(long, default_value_t = 0)]
+        suggest: usize,
+    },
+}
+
+// play against an Absurdle style host, reading guesses from stdin
+fn play_absurdle(words: WordleWords) {
+    let mut host = Absurdle::new(words);
+    println!(
+        "{} possible words, enter a guess (or quit)",
+        host.get_word_list().len()
+    );
+    for line in io::stdin().lock().lines() {
+        let guess = line.unwrap().trim().to_lowercase();
+        if guess == "quit" {
+            break;
+        }
+        match host.guess(&guess) {
+            Ok(feedback) => println!(
+                "{} {} ({} words left)",
+                guess,
+                feedback,
+                host.get_word_list().len()
+            ),
+            Err(e) => println!("{}", e),
+        }
+        if host.is_won() {
+            println!("won in {} guesses", host.get_guesses().len());
+            break;
+        }
+    }
+}
+
+// split guesses in "crane:bygbb slate:bbbyg" format into guesses and their
+// feedback
+fn parse_guesses(text: &str) -> Vec<(String, String)> {
+    let mut guesses = Vec::new();
+    let mut items = text.split_whitespace();
+    while let Some(item) = items.next() {
+        // allow a space after the ':', as in "crane: 2"
+        let item = match (item.ends_with(':'), items.clone().next()) {
+            (true, Some(feedback)) => {
+                items.next();
+                format!("{}{}", item, feedback)
+            }
+            _ => item.to_string(),
+        };
+        match item.split_once(':') {
+            Some((guess, feedback)) => guesses.push((guess.to_string(), feedback.to_string())),
+            None => println!("guess {} is not in guess:feedback format", item),
+        }
+    }
+    guesses
 }
 
 fn main() {
@@ -42,47 +276,332 @@ fn main() {
 
     // println!("path is {}", args.filename.display());
 
-    // read the contents of the filename line by line
-    let lines = BufReader::new(File::open(args.filename).unwrap()).lines();
+    let (v, frequencies) = match (&args.codes, args.nerdle) {
+        // every code of the symbols, rather than a list of words
+        (Some(symbols), _) => match wordle::generate_codes(symbols, args.length, !args.no_repeats) {
+            Ok(codes) => (codes, HashMap::new()),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        // every equation of the length
+        (None, Some(length)) => match nerdle::equations(length) {
+            Ok(equations) => (equations, HashMap::new()),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        (None, None) => {
+            // read the contents of the filename line by line
+            let lines = BufReader::new(File::open(&args.filename).unwrap()).lines();
+
+            let mut v = Vec::new();
+            for line in lines {
+                v.push(line.unwrap());
+            }
+            // each line has a word, and maybe how often it is used
+            wordle::read_word_list(v)
+        }
+    };
+    // codes are scored with pegs, unless told otherwise
+    let feedback = args
+        .feedback
+        .or(args.codes.as_ref().map(|_| FeedbackMode::Pegs));
 
-    let mut v = Vec::new();
-    for line in lines {
-        v.push(line.unwrap());
+    // words banned or pinned by the user, updated with the options
+    let user_path = UserList::default_path();
+    let mut user_list = UserList::load(&user_path).unwrap_or_else(|e| {
+        eprintln!("unable to read {}: {}", user_path.display(), e);
+        UserList::new(&user_path)
+    });
+    let mut changed = false;
+    if let Some(words) = &args.ban {
+        words.split_whitespace().for_each(|word| user_list.ban(word));
+        changed = true;
+    }
+    if let Some(words) = &args.unban {
+        words.split_whitespace().for_each(|word| user_list.unban(word));
+        changed = true;
+    }
+    if let Some(words) = &args.pin {
+        words.split_whitespace().for_each(|word| user_list.pin(word));
+        changed = true;
+    }
+    if let Some(words) = &args.unpin {
+        words.split_whitespace().for_each(|word| user_list.unpin(word));
+        changed = true;
+    }
+    if changed {
+        if let Err(e) = user_list.save() {
+            eprintln!("unable to save {}: {}", user_path.display(), e);
+        }
     }
 
+    let word_list = match (&args.codes, args.nerdle) {
+        (Some(symbols), _) => format!("codes {} {}", symbols, args.length),
+        (None, Some(length)) => format!("nerdle {}", length),
+        (None, None) => args.filename.display().to_string(),
+    };
+    let word_count = v.len();
     let mut possible_list = WordleWords::new(v);
+    possible_list.set_frequencies(frequencies);
+
+    // words that were the answer before
+    if let Some(path) = &args.past {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                let lines = contents.lines().map(|line| line.to_string()).collect();
+                let answers = wordle::read_past_answers(lines);
+                possible_list.set_past_answers(answers, args.downweight_past);
+            }
+            Err(e) => eprintln!("unable to read {}: {}", path.display(), e),
+        }
+    }
+
+    // the banned and pinned words are words, not codes or equations
+    if args.codes.is_none() && args.nerdle.is_none() {
+        user_list.apply(&mut possible_list);
+    }
+
+    if let Some(Command::Serve { port }) = args.command {
+        possible_list.set_hard_mode(args.hard);
+        possible_list.set_lies(args.lies.unwrap_or(0));
+        possible_list.set_feedback_mode(feedback.unwrap_or_default());
+        let mut server = server::Server::new(possible_list);
+
+        server.start(port);
+        return;
+    }
+
+    if let Some(Command::Analyze { guesses, json }) = &args.command {
+        possible_list.set_hard_mode(args.hard);
+        possible_list.set_feedback_mode(feedback.unwrap_or_default());
+        match analysis::analyze(&possible_list, &parse_guesses(guesses)) {
+            Ok(steps) if *json => println!("{}", serde_json::to_string_pretty(&steps).unwrap()),
+            Ok(steps) => println!("{}", analysis::report(&steps)),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(Command::Absurdle { solve, width, max_guesses }) = &args.command {
+        if *solve {
+            match absurdle::solve(&possible_list, *width, *max_guesses) {
+                Some(guesses) => println!("{} ({} guesses)", guesses.join(" "), guesses.len()),
+                None => println!("no win found in {} guesses", max_guesses),
+            }
+        } else {
+            play_absurdle(possible_list);
+        }
+        return;
+    }
+
+    if let Some(Command::Batch { log }) = &args.command {
+        let games = fs::read_to_string(log)
+            .map_err(|e| format!("unable to read {}: {}", log.display(), e))
+            .and_then(|contents| analysis::read_games(&contents));
+        match games {
+            Ok(games) => {
+                let (summaries, errors) = analysis::summarize(&possible_list, &games);
+                for error in errors {
+                    eprintln!("skipping game of {}", error);
+                }
+                print!("{}", analysis::summary_csv(&summaries));
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(Command::Reverse { answer, grid }) = &args.command {
+        let grid = match grid {
+            Some(grid) => grid.clone(),
+            None => io::read_to_string(io::stdin()).unwrap(),
+        };
+        let rows = share::parse_grid(&grid);
+        match share::reverse(&possible_list, answer, &rows, args.hard) {
+            Ok(candidates) => {
+                for (index, (row, words)) in rows.iter().zip(candidates).enumerate() {
+                    let mut line = format!("{}. {} {} words", index + 1, row, words.len());
+                    if !words.is_empty() {
+                        line.push_str(&format!(": {}", words.join(" ")));
+                    }
+                    println!("{}", line);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(Command::Infer { grids, top }) = &args.command {
+        let text = match grids {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| format!("unable to read {}: {}", path.display(), e)),
+            None => io::read_to_string(io::stdin()).map_err(|e| e.to_string()),
+        };
+        match text {
+            Ok(text) => {
+                let grids = share::parse_grids(&text);
+                for (answer, count) in share::infer_answers(&possible_list, &grids).iter().take(*top) {
+                    println!("{} {}/{} grids", answer, count, grids.len());
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(Command::Xordle { guesses, suggest }) = &args.command {
+        let mut game = Xordle::new(&possible_list);
+        for (guess, feedback) in parse_guesses(guesses) {
+            if let Err(e) = game.apply_guess(&guess, &feedback) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        let pairs = game.get_pairs();
+        for (first, second) in &pairs {
+            println!("{} {}", first, second);
+        }
+        println!("{} possible pairs", pairs.len());
+        for s in game.suggest(*suggest) {
+            let mark = if s.possible { " *" } else { "" };
+            println!(
+                "{} {:.2} bits, {} left at worst{}",
+                s.word, s.information, s.worst_case, mark
+            );
+        }
+        return;
+    }
+
+    // start from the saved session, if there is one
+    let mut session = match &args.session {
+        Some(path) if path.exists() => {
+            let session = match Session::load(path) {
+                Ok(session) => session,
+                Err(e) => {
+                    eprintln!("unable to read {}: {}", path.display(), e);
+                    process::exit(1);
+                }
+            };
+            if !session.same_word_list(&word_list, word_count) {
+                eprintln!(
+                    "session was saved with {} ({} words), not {} ({} words)",
+                    session.word_list, session.word_count, word_list, word_count
+                );
+            }
+            session
+        }
+        _ => Session::new(&word_list, word_count),
+    };
+    session.word_list = word_list;
+    session.word_count = word_count;
+    if args.hard {
+        session.hard_mode = true;
+    }
+    if let Some(boards) = args.boards {
+        session.boards = boards;
+    }
+    if let Some(lies) = args.lies {
+        session.lies = lies;
+    }
+    if let Some(feedback) = feedback {
+        session.feedback = feedback;
+    }
 
     if args.tui {
-        let mut tui = tui::Tui::new(possible_list);
+        if session.feedback != FeedbackMode::Colors || args.codes.is_some() || args.nerdle.is_some() {
+            eprintln!("the TUI only supports words with colors feedback");
+            process::exit(1);
+        }
+        let mut tui = tui::Tui::new(possible_list, session, args.rows, user_list);
 
         tui.start();
     } else {
-        match args.exclude {
-            Some(x) => possible_list.remove_letters(&x),
-            _ => (),
+
+        if let Some(x) = args.exclude {
+            session.add_exclude(&x);
         }
 
-        match args.correct {
-            Some(x) => possible_list.correct_letters(&x),
-            _ => (),
+        if let Some(x) = args.correct {
+            if let Err(e) = session.add_correct(&x) {
+                eprintln!("{}", e);
+            }
         }
 
-        match args.incorrect {
-            Some(x) => {
-                // println!("String {}", x);
-                let my_array: Vec<&str> = x.as_str().split(" ").collect();
-                // println!("my_array {:?}", my_array);
-                for word in my_array {
-                    // println!("Val {}", word);
-                    possible_list.incorrect_letters(&word);
-                }
+        if let Some(x) = args.incorrect {
+            // println!("String {}", x);
+            let my_array: Vec<&str> = x.as_str().split(' ').collect();
+            // println!("my_array {:?}", my_array);
+            for word in my_array {
+                // println!("Val {}", word);
+                session.incorrect.push(word.to_string());
             }
-            _ => (),
         }
 
-        // println!("{:?}", possible_list.get_word_list());
-        for line in possible_list.get_word_list() {
-            println!("{}", line);
+        if let Some(x) = args.guess {
+            session.guesses.extend(parse_guesses(&x));
+        }
+
+        if let Some(query) = args.query {
+            if let Err(e) = possible_list.check_query(&query) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+            session.query = query;
+        }
+
+        if let Some(letters) = args.anagram {
+            session.anagram = letters;
+        }
+        if let Some(letters) = args.only {
+            session.only = letters;
+            session.only_once = args.only_once;
+        }
+
+        if let Err(e) = session.apply(&mut possible_list) {
+            eprintln!("{}", e);
+        }
+
+        if args.repl {
+            let mut repl = repl::Repl::new(possible_list);
+
+            repl.start();
+            possible_list = repl.into_solver();
+        } else if args.stdio {
+            let mut stdio = stdio::Stdio::new(possible_list);
+
+            stdio.start();
+            possible_list = stdio.into_solver();
+        } else {
+            // println!("{:?}", possible_list.get_word_list());
+            for line in possible_list.get_word_list() {
+                println!("{}", line);
+            }
+        }
+
+        // only keep the guesses that were applied
+        session.guesses = possible_list.get_guesses().clone();
+        if let Some(path) = &args.session {
+            if let Err(e) = session.save(path) {
+                eprintln!("unable to save {}: {}", path.display(), e);
+                process::exit(1);
+            }
         }
     }
 }
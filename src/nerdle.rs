@@ -0,0 +1,266 @@
+// Nerdle: Wordle with arithmetic equations, such as "12+35=47", instead
+// of words. The feedback is the same, so every valid equation of the
+// length being played is generated and used as the list of words.
+
+/// The shortest equation length: the shortest expression is "1+2", and
+/// the value needs a digit and the '='.
+pub const MIN_LENGTH: usize = 5;
+
+/// The longest equation length. There are about 290,000 equations of 9
+/// characters, too many for suggestions to be quick.
+pub const MAX_LENGTH: usize = 8;
+
+/// Returns the whole number value of an expression of numbers and the
+/// operators '+', '-', '*' and '/', or None if it is not a valid
+/// expression or its value is not a whole number.
+///
+/// '*' and '/' are worked out before '+' and '-', from left to right.
+/// Divisions do not have to be exact, as long as the value is a whole
+/// number (e.g. "3/2*4" is 6). Numbers cannot have leading zeros.
+///
+/// # Example
+/// ```
+/// use wordlesolver::nerdle::evaluate;
+///
+/// assert_eq!(evaluate("12+35"), Some(47));
+/// assert_eq!(evaluate("2+3*4"), Some(14));
+/// assert_eq!(evaluate("3/2*4"), Some(6));
+/// assert_eq!(evaluate("3/2"), None);
+/// assert_eq!(evaluate("1/0"), None);
+/// assert_eq!(evaluate("05+1"), None);
+/// ```
+pub fn evaluate(expression: &str) -> Option<i64> {
+    let (numerator, denominator) = value(&tokens(expression)?)?;
+    if numerator % denominator == 0 {
+        Some(numerator / denominator)
+    } else {
+        None
+    }
+}
+
+/// Returns true if the equation is a valid Nerdle equation: an
+/// expression with at least one operator and no lone zeros (e.g. "0+5"),
+/// '=', and its value as a number that is not negative.
+///
+/// # Example
+/// ```
+/// use wordlesolver::nerdle::is_valid_equation;
+///
+/// assert!(is_valid_equation("12+35=47"));
+/// assert!(is_valid_equation("18-2*9=0"));
+/// assert!(!is_valid_equation("12+35=48"));
+/// assert!(!is_valid_equation("47=12+35"));
+/// assert!(!is_valid_equation("47=47"));
+/// assert!(!is_valid_equation("1-2=-1"));
+/// assert!(!is_valid_equation("10*0+5=5"));
+/// ```
+pub fn is_valid_equation(equation: &str) -> bool {
+    let (left, right) = match equation.split_once('=') {
+        Some(sides) => sides,
+        None => return false,
+    };
+    match tokens(left) {
+        Some(left) if left.len() > 1 && !left.contains(&Token::Number(0)) => (),
+        _ => return false,
+    }
+    match (evaluate(left), tokens(right).as_deref()) {
+        (Some(result), Some([Token::Number(number)])) => result == *number,
+        _ => false,
+    }
+}
+
+/// Returns every valid Nerdle equation (see [`is_valid_equation`]) with
+/// the number of characters given, e.g. 8 for Nerdle or 6 for Mini
+/// Nerdle.
+///
+/// Returns an error if the length is not from [`MIN_LENGTH`] to
+/// [`MAX_LENGTH`].
+///
+/// # Example
+/// ```
+/// use wordlesolver::nerdle::equations;
+///
+/// let result = equations(5).unwrap();
+/// assert!(result.contains(&String::from("1+2=3")));
+/// assert!(result.contains(&String::from("8/4=2")));
+/// assert!(result.iter().all(|e| e.len() == 5));
+/// assert!(equations(4).is_err());
+/// ```
+pub fn equations(length: usize) -> Result<Vec<String>, String> {
+    if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+        return Err(format!(
+            "the length of the equations has to be from {} to {}",
+            MIN_LENGTH, MAX_LENGTH
+        ));
+    }
+    let mut result = Vec::new();
+    for left_length in 3..=length - 2 {
+        let mut left = String::new();
+        expressions(&mut left, left_length, &mut |expression| {
+            if let Some(value) = evaluate(expression) {
+                let right = value.to_string();
+                if value >= 0 && left_length + 1 + right.len() == length {
+                    result.push(format!("{}={}", expression, right));
+                }
+            }
+        });
+    }
+    Ok(result)
+}
+
+// Calls found with every expression of numbers and operators of the
+// given length, with at least one operator and no numbers starting with
+// '0', that starts with expression.
+fn expressions(expression: &mut String, length: usize, found: &mut dyn FnMut(&str)) {
+    if expression.len() == length {
+        let has_operator = expression.contains(['+', '-', '*', '/']);
+        if has_operator && expression.ends_with(|c: char| c.is_ascii_digit()) {
+            found(expression);
+        }
+        return;
+    }
+
+    // a '0' can only follow another digit, as there are no leading or
+    // lone zeros
+    let in_number = expression.ends_with(|c: char| c.is_ascii_digit());
+    let mut next: Vec<char> = ('1'..='9').collect();
+    if in_number {
+        next.push('0');
+        next.extend(['+', '-', '*', '/']);
+    }
+    for c in next {
+        expression.push(c);
+        expressions(expression, length, found);
+        expression.pop();
+    }
+}
+
+// A number or an operator of an expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number(i64),
+    Operator(char),
+}
+
+// Splits an expression into numbers and operators, which have to
+// alternate, starting and ending with a number. Returns None if the
+// expression has other characters, or a number has leading zeros.
+fn tokens(expression: &str) -> Option<Vec<Token>> {
+    let mut result = Vec::new();
+    let mut number = String::new();
+    for c in expression.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+        } else if "+-*/".contains(c) {
+            result.push(Token::Number(parse_number(&number)?));
+            result.push(Token::Operator(c));
+            number.clear();
+        } else {
+            return None;
+        }
+    }
+    result.push(Token::Number(parse_number(&number)?));
+    Some(result)
+}
+
+// Returns the value of a number without leading zeros.
+fn parse_number(number: &str) -> Option<i64> {
+    if number.is_empty() || (number.len() > 1 && number.starts_with('0')) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+// Returns the value of the tokens as a fraction, numerator and
+// denominator (always positive), or None on a division by zero or an
+// overflow.
+fn value(tokens: &[Token]) -> Option<(i64, i64)> {
+    // sum of the terms worked out so far, and the current term
+    let mut sum: (i64, i64) = (0, 1);
+    let mut term: (i64, i64) = (0, 1);
+    let mut sign = 1;
+    let mut operator = '+';
+    for token in tokens {
+        match *token {
+            Token::Operator(c) => operator = c,
+            Token::Number(n) => match operator {
+                '*' => term = reduce(term.0.checked_mul(n)?, term.1)?,
+                '/' => term = reduce(term.0, term.1.checked_mul(n)?)?,
+                _ => {
+                    sum = add(sum, (sign * term.0, term.1))?;
+                    sign = if operator == '-' { -1 } else { 1 };
+                    term = (n, 1);
+                }
+            },
+        }
+    }
+    add(sum, (sign * term.0, term.1))
+}
+
+// Returns the sum of two fractions.
+fn add(a: (i64, i64), b: (i64, i64)) -> Option<(i64, i64)> {
+    let numerator = a.0.checked_mul(b.1)?.checked_add(b.0.checked_mul(a.1)?)?;
+    reduce(numerator, a.1.checked_mul(b.1)?)
+}
+
+// Returns a fraction in its lowest terms, or None if the denominator is
+// zero.
+fn reduce(numerator: i64, denominator: i64) -> Option<(i64, i64)> {
+    if denominator == 0 {
+        return None;
+    }
+    let divisor = gcd(numerator.abs(), denominator.abs());
+    Some((numerator / divisor, denominator / divisor))
+}
+
+// Returns the greatest common divisor of two numbers, that are not both
+// zero.
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::wordle::WordleWords;
+
+    #[test]
+    fn evaluate_test_1() {
+        assert_eq!(evaluate("10-2*3"), Some(4));
+        assert_eq!(evaluate("2-3"), Some(-1));
+        assert_eq!(evaluate("8/4/2"), Some(1));
+        assert_eq!(evaluate("0*5+1"), Some(1));
+        assert_eq!(evaluate("1++2"), None);
+        assert_eq!(evaluate("+1"), None);
+        assert_eq!(evaluate("1+"), None);
+        assert_eq!(evaluate(""), None);
+    }
+
+    #[test]
+    fn equations_test_1() {
+        let result = equations(6).unwrap();
+        assert!(result.iter().all(|e| is_valid_equation(e)));
+        assert!(result.contains(&String::from("10-2=8")));
+        assert!(result.contains(&String::from("4*5=20")));
+        // the value cannot be negative, and there are no lone zeros
+        assert!(!result.contains(&String::from("1-10=-9")));
+        assert!(!result.contains(&String::from("10*0=0")));
+        assert!(equations(5).unwrap().contains(&String::from("3-3=0")));
+        assert!(equations(4).is_err());
+        assert!(equations(9).is_err());
+    }
+
+    #[test]
+    fn solver_test_1() {
+        // the feedback to Nerdle guesses is the same as for words
+        let mut list = WordleWords::new(equations(5).unwrap());
+        list.apply_guess("1+2=3", "ggbgb").unwrap();
+        let expected = vec!["1+4=5", "1+5=6", "1+6=7", "1+7=8", "1+8=9"];
+        assert_eq!(list.get_word_list(), &expected);
+    }
+}
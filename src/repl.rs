@@ -0,0 +1,288 @@
+use std::io::{self, IsTerminal};
+
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::wordle::{FeedbackMode, WordleWords};
+
+// commands understood by the REPL, also used for tab completion
+const COMMANDS: [&str; 10] = [
+    "guess", "list", "suggest", "why", "undo", "reset", "stats", "help", "quit", "exit",
+];
+
+const HELP: &str = "\
+guess WORD FEEDBACK  apply a guess, e.g. 'guess crane bygbb' ('g'reen, 'y'ellow, 'b'lack), or 'guess crane 2' with count feedback
+list                 list the possible words
+suggest [N]          suggest the N (default 5) best next guesses, * marks possible words
+why WORD             explain why a word is, or is not, possible
+undo                 remove the last guess
+reset                remove all guesses
+stats                show the number of words and guesses
+help                 show this help
+quit                 leave the REPL";
+
+// number of words printed on each line by 'list'
+const WORDS_PER_LINE: usize = 10;
+
+/// A line oriented interface to the list of possible words.
+pub struct Repl {
+    solver: WordleWords,
+    // whether to use colors in the output
+    color: bool,
+}
+
+impl Repl {
+    pub fn new(solver: WordleWords) -> Repl {
+        Repl {
+            solver,
+            color: io::stdout().is_terminal(),
+        }
+    }
+
+    /// Returns the list of possible words, with the guesses made in the
+    /// REPL applied.
+    pub fn into_solver(self) -> WordleWords {
+        self.solver
+    }
+
+    pub fn start(&mut self) {
+        let mut editor: Editor<WordHelper, DefaultHistory> = Editor::new().unwrap();
+        editor.set_helper(Some(WordHelper {
+            words: self.solver.get_original_list().clone(),
+        }));
+
+        println!("Type 'help' for a list of commands");
+        // stop at the end of input or ctrl-c
+        while let Ok(line) = editor.readline("wordle> ") {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let _ = editor.add_history_entry(line.as_str());
+            match self.execute(&line) {
+                Some(output) => println!("{}", output),
+                None => break,
+            }
+        }
+    }
+
+    /// Runs one command and returns its output, or None if the REPL
+    /// should stop.
+    pub fn execute(&mut self, line: &str) -> Option<String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let output = match parts.as_slice() {
+            ["guess", guess, feedback] => self.guess(guess, feedback),
+            ["list"] => self.list(),
+            ["suggest"] => self.suggest(5),
+            ["suggest", count] => match count.parse() {
+                Ok(count) => self.suggest(count),
+                Err(_) => format!("{} is not a number", count),
+            },
+            ["why", word] => self.solver.explain(word),
+            ["undo"] => match self.solver.undo_guess() {
+                Some((guess, feedback)) => format!(
+                    "removed {}, {} possible words",
+                    self.tiles(&guess, &feedback),
+                    self.solver.get_word_list().len()
+                ),
+                None => String::from("no guesses to undo"),
+            },
+            ["reset"] => {
+                self.solver.clear_guesses();
+                format!("{} possible words", self.solver.get_word_list().len())
+            }
+            ["stats"] => self.stats(),
+            ["help"] => String::from(HELP),
+            ["quit"] | ["exit"] => return None,
+            _ => format!("unknown command: {}, type 'help' for a list of commands", line.trim()),
+        };
+        Some(output)
+    }
+
+    fn guess(&mut self, guess: &str, feedback: &str) -> String {
+        if let Err(e) = self.solver.apply_guess(guess, feedback) {
+            return e;
+        }
+        format!(
+            "{} {} possible words",
+            self.tiles(guess, feedback),
+            self.solver.get_word_list().len()
+        )
+    }
+
+    fn list(&self) -> String {
+        let words = self.solver.get_word_list();
+        let mut lines: Vec<String> = words
+            .chunks(WORDS_PER_LINE)
+            .map(|chunk| chunk.join(" "))
+            .collect();
+        lines.push(format!("{} possible words", words.len()));
+        lines.join("\n")
+    }
+
+    fn suggest(&self, count: usize) -> String {
+        let suggestions = self.solver.suggest(count);
+        if suggestions.is_empty() {
+            return String::from("no possible words");
+        }
+        suggestions
+            .iter()
+            .map(|s| {
+                if s.possible {
+                    format!(
+                        "{} {:.2} bits, {} left at worst *",
+                        self.paint(&s.word, "32"),
+                        s.information,
+                        s.worst_case
+                    )
+                } else {
+                    format!(
+                        "{} {:.2} bits, {} left at worst",
+                        s.word, s.information, s.worst_case
+                    )
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn stats(&self) -> String {
+        let possible = self.solver.get_word_list().len();
+        let mut lines = vec![
+            format!("words:    {}", self.solver.get_original_list().len()),
+            format!("possible: {}", possible),
+            format!("guesses:  {}", self.solver.get_guesses().len()),
+        ];
+        if possible > 0 {
+            lines.push(format!("unknown:  {:.2} bits", (possible as f64).log2()));
+        }
+        for (guess, feedback) in self.solver.get_guesses() {
+            lines.push(self.tiles(guess, feedback));
+        }
+        lines.join("\n")
+    }
+
+    // show a guess as colored tiles, or as "guess/feedback" without colors
+    // or when the feedback is not colors
+    fn tiles(&self, guess: &str, feedback: &str) -> String {
+        if !self.color || self.solver.get_feedback_mode() != FeedbackMode::Colors {
+            return format!("{}/{}", guess, feedback);
+        }
+        guess
+            .chars()
+            .zip(feedback.chars())
+            .map(|(letter, color)| {
+                let code = match color {
+                    'g' => "30;42",
+                    'y' => "30;43",
+                    _ => "37;100",
+                };
+                self.paint(&letter.to_uppercase().to_string(), code)
+            })
+            .collect()
+    }
+
+    // wrap text in an ANSI color code
+    fn paint(&self, text: &str, code: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// completes commands at the start of the line and words after that
+struct WordHelper {
+    words: Vec<String>,
+}
+
+impl Completer for WordHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos].rfind(' ').map_or(0, |i| i + 1);
+        let prefix = &line[start..pos];
+        let candidates = if start == 0 {
+            COMMANDS
+                .iter()
+                .filter(|c| c.starts_with(prefix))
+                .map(|c| c.to_string())
+                .collect()
+        } else {
+            self.words
+                .iter()
+                .filter(|w| w.starts_with(prefix))
+                .cloned()
+                .collect()
+        };
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for WordHelper {
+    type Hint = String;
+}
+
+impl Highlighter for WordHelper {}
+
+impl Validator for WordHelper {}
+
+impl Helper for WordHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_repl() -> Repl {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ];
+        Repl {
+            solver: WordleWords::new(v),
+            color: false,
+        }
+    }
+
+    #[test]
+    fn guess_undo_test_1() {
+        let mut repl = new_repl();
+        assert_eq!(
+            repl.execute("guess crane yygby").unwrap(),
+            "crane/yygby 1 possible words"
+        );
+        assert_eq!(repl.execute("list").unwrap(), "react\n1 possible words");
+        assert_eq!(
+            repl.execute("undo").unwrap(),
+            "removed crane/yygby, 3 possible words"
+        );
+        assert_eq!(repl.execute("undo").unwrap(), "no guesses to undo");
+    }
+
+    #[test]
+    fn command_test_1() {
+        let mut repl = new_repl();
+        assert_eq!(
+            repl.execute("guess crane yyg").unwrap(),
+            "feedback yyg is not valid for guess crane"
+        );
+        assert_eq!(
+            repl.execute("why trace").unwrap(),
+            "trace is a possible word"
+        );
+        assert!(repl.execute("suggest 2").unwrap().lines().count() == 2);
+        assert!(repl.execute("suggest two").unwrap().contains("not a number"));
+        assert!(repl.execute("jump").unwrap().starts_with("unknown command"));
+        assert!(repl.execute("quit").is_none());
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Response};
+
+use crate::api;
+use crate::wordle::WordleWords;
+
+/// A local HTTP server answering JSON requests about the list of
+/// possible words.
+///
+/// Each session has its own list of possible words, starting from a
+/// copy of the words the server was created with:
+///
+/// - `POST /sessions` with an optional `{"hard": true}` body creates a
+///   session, in hard mode if the words are not already, and returns its
+///   `id`
+/// - `POST /sessions/{id}/guess` with a `{"guess": "crane", "feedback":
+///   "bygbb"}` body applies a guess
+/// - `GET /sessions/{id}/candidates` returns the possible words
+/// - `GET /sessions/{id}/suggestions?n=5` returns the best next guesses
+/// - `DELETE /sessions/{id}` removes the session
+/// - `POST /shutdown` stops the server
+pub struct Server {
+    // list of words, with its feedback mode and options, every session
+    // starts from
+    words: WordleWords,
+    sessions: HashMap<u64, WordleWords>,
+    // id of the next session to be created
+    next_id: u64,
+    // cleared when the server should stop
+    running: bool,
+}
+
+#[derive(Deserialize, Default)]
+struct NewSession {
+    #[serde(default)]
+    hard: bool,
+}
+
+#[derive(Deserialize)]
+struct Guess {
+    guess: String,
+    feedback: String,
+}
+
+impl Server {
+    pub fn new(words: WordleWords) -> Server {
+        Server {
+            words,
+            sessions: HashMap::new(),
+            next_id: 1,
+            running: true,
+        }
+    }
+
+    /// Listens on localhost until a `POST /shutdown` request is received.
+    pub fn start(&mut self, port: u16) {
+        let server = match tiny_http::Server::http(("127.0.0.1", port)) {
+            Ok(server) => server,
+            Err(e) => {
+                eprintln!("unable to listen on port {}: {}", port, e);
+                return;
+            }
+        };
+        println!("Listening on http://127.0.0.1:{}", port);
+
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let (status, value) = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => self.handle(request.method(), request.url(), &body),
+                Err(e) => error(400, &format!("unable to read request: {}", e)),
+            };
+            let response = Response::from_string(value.to_string())
+                .with_status_code(status)
+                .with_header(
+                    Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
+                );
+            if let Err(e) = request.respond(response) {
+                eprintln!("unable to send response: {}", e);
+            }
+            if !self.running {
+                break;
+            }
+        }
+        println!("Server stopped");
+    }
+
+    // Handles one request, returning the HTTP status and the JSON to send
+    // back.
+    fn handle(&mut self, method: &Method, url: &str, body: &str) -> (u16, Value) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+
+        match (method, parts.as_slice()) {
+            (Method::Post, ["sessions"]) => self.new_session(body),
+            (Method::Post, ["shutdown"]) => {
+                self.running = false;
+                (200, json!({}))
+            }
+            (method, ["sessions", id, rest @ ..]) => {
+                let words = match id.parse().ok().and_then(|id| self.sessions.get_mut(&id)) {
+                    Some(words) => words,
+                    None => return error(404, &format!("no session {}", id)),
+                };
+                match (method, rest) {
+                    (Method::Post, ["guess"]) => guess(words, body),
+                    (Method::Get, ["candidates"]) => (200, api::candidates(words)),
+                    (Method::Get, ["suggestions"]) => suggestions(words, query),
+                    (Method::Delete, []) => {
+                        let id: u64 = id.parse().unwrap();
+                        self.sessions.remove(&id);
+                        (200, json!({}))
+                    }
+                    _ => error(404, &format!("unknown request {} {}", method, path)),
+                }
+            }
+            _ => error(404, &format!("unknown request {} {}", method, path)),
+        }
+    }
+
+    fn new_session(&mut self, body: &str) -> (u16, Value) {
+        let request: NewSession = if body.trim().is_empty() {
+            NewSession::default()
+        } else {
+            match serde_json::from_str(body) {
+                Ok(request) => request,
+                Err(e) => return error(400, &format!("invalid request: {}", e)),
+            }
+        };
+
+        let mut words = self.words.clone();
+        if request.hard {
+            words.set_hard_mode(true);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let count = words.get_word_list().len();
+        self.sessions.insert(id, words);
+        (200, json!({ "id": id, "count": count }))
+    }
+}
+
+fn guess(words: &mut WordleWords, body: &str) -> (u16, Value) {
+    let request: Guess = match serde_json::from_str(body) {
+        Ok(request) => request,
+        Err(e) => return error(400, &format!("invalid request: {}", e)),
+    };
+    match api::guess(words, &request.guess, &request.feedback) {
+        Ok(value) => (200, value),
+        Err(message) => error(400, &message),
+    }
+}
+
+fn suggestions(words: &WordleWords, query: &str) -> (u16, Value) {
+    let mut count = api::DEFAULT_SUGGESTIONS;
+    for pair in query.split('&') {
+        if let Some(("n", value)) = pair.split_once('=') {
+            match value.parse() {
+                Ok(n) => count = n,
+                Err(_) => return error(400, &format!("{} is not a number", value)),
+            }
+        }
+    }
+    (200, api::suggestions(words, count))
+}
+
+// JSON for an error response
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, api::error(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::wordle::FeedbackMode;
+
+    fn new_server() -> Server {
+        Server::new(WordleWords::new(vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ]))
+    }
+
+    #[test]
+    fn session_test_1() {
+        let mut server = new_server();
+        let (status, value) = server.handle(&Method::Post, "/sessions", "");
+        assert_eq!(status, 200);
+        assert_eq!(value, json!({ "id": 1, "count": 3 }));
+
+        let body = r#"{"guess": "crane", "feedback": "yygby"}"#;
+        let (status, value) = server.handle(&Method::Post, "/sessions/1/guess", body);
+        assert_eq!(status, 200);
+        assert_eq!(value, json!({ "count": 1 }));
+
+        let (_, value) = server.handle(&Method::Get, "/sessions/1/candidates", "");
+        assert_eq!(value, json!({ "count": 1, "candidates": ["react"] }));
+
+        let (_, value) = server.handle(&Method::Get, "/sessions/1/suggestions?n=1", "");
+        assert_eq!(value["suggestions"][0]["word"], "react");
+        assert_eq!(value["suggestions"][0]["possible"], true);
+
+        let (status, _) = server.handle(&Method::Delete, "/sessions/1", "");
+        assert_eq!(status, 200);
+        let (status, _) = server.handle(&Method::Get, "/sessions/1/candidates", "");
+        assert_eq!(status, 404);
+    }
+
+    #[test]
+    fn error_test_1() {
+        let mut server = new_server();
+        server.handle(&Method::Post, "/sessions", r#"{"hard": true}"#);
+        let body = r#"{"guess": "crane", "feedback": "yyg"}"#;
+        let (status, _) = server.handle(&Method::Post, "/sessions/1/guess", body);
+        assert_eq!(status, 400);
+        let (status, _) = server.handle(&Method::Post, "/sessions/1/guess", "crane");
+        assert_eq!(status, 400);
+        let (status, _) = server.handle(&Method::Get, "/sessions/1/suggestions?n=x", "");
+        assert_eq!(status, 400);
+        let (status, _) = server.handle(&Method::Get, "/sessions/2/candidates", "");
+        assert_eq!(status, 404);
+        let (status, _) = server.handle(&Method::Get, "/words", "");
+        assert_eq!(status, 404);
+
+        assert!(server.running);
+        server.handle(&Method::Post, "/shutdown", "");
+        assert!(!server.running);
+    }
+
+    #[test]
+    fn feedback_mode_test_1() {
+        // sessions use the feedback mode of the words the server has
+        let mut words = WordleWords::new(vec![String::from("react"), String::from("bored")]);
+        words.set_feedback_mode(FeedbackMode::Count);
+        let mut server = Server::new(words);
+        server.handle(&Method::Post, "/sessions", "");
+        let body = r#"{"guess": "crane", "feedback": "2"}"#;
+        let (status, value) = server.handle(&Method::Post, "/sessions/1/guess", body);
+        assert_eq!(status, 200);
+        assert_eq!(value, json!({ "count": 1 }));
+    }
+}
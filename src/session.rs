@@ -0,0 +1,516 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::wordle::{self, FeedbackMode, LetterState, WordleWords};
+
+/// Structure to hold the state of a game so that it can be saved to,
+/// and loaded from, a file.
+///
+/// The file is a simple text file with one `key: value` entry per
+/// line, for example:
+///
+/// ```text
+/// # wordlesolver session
+/// wordlist: wordle.list
+/// words: 2315
+/// hard: false
+/// boards: 1
+/// lies: 0
+/// feedback: colors
+/// exclude: steam
+/// correct: b..o.
+/// incorrect: d....
+/// incorrect: ..e..
+/// guess: crane bybbb
+/// remove: boxes
+/// query: ?r??e -s
+/// anagram: tacer
+/// only: acerti
+/// only_once: true
+/// ```
+///
+/// `incorrect`, `guess` and `remove` can appear more than once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    /// name of the file the list of words was read from
+    pub word_list: String,
+    /// number of words in the list, to detect a different list
+    pub word_count: usize,
+    /// whether guesses have to follow the hard mode rules
+    pub hard_mode: bool,
+    /// number of boards being played (e.g. 2 for Dordle)
+    pub boards: usize,
+    /// number of tiles of each feedback that can be wrong (e.g. 1 for
+    /// Fibble)
+    pub lies: usize,
+    /// how the game scores a guess, colors or the count of common letters
+    pub feedback: FeedbackMode,
+    /// letters not in the word
+    pub exclude: String,
+    /// letters in the correct position, '.' for those not yet known
+    pub correct: String,
+    /// letters in incorrect positions, '.' for those not yet known
+    pub incorrect: Vec<String>,
+    /// guesses and their feedback
+    pub guesses: Vec<(String, String)>,
+    /// words ruled out by hand
+    pub removed: Vec<String>,
+    /// crossword style query the words have to match, empty for none
+    pub query: String,
+    /// letters the words have to be anagrams of, empty for none
+    pub anagram: String,
+    /// letters the words can only use, empty for none
+    pub only: String,
+    /// whether each letter of only can be used at most as many times as
+    /// it is given
+    pub only_once: bool,
+}
+
+impl Session {
+    /// Returns a new, empty, session for the list of words.
+    pub fn new(word_list: &str, word_count: usize) -> Session {
+        Session {
+            word_list: word_list.to_string(),
+            word_count,
+            hard_mode: false,
+            boards: 1,
+            lies: 0,
+            feedback: FeedbackMode::Colors,
+            exclude: String::new(),
+            correct: String::from("....."),
+            incorrect: Vec::new(),
+            guesses: Vec::new(),
+            removed: Vec::new(),
+            query: String::new(),
+            anagram: String::new(),
+            only: String::new(),
+            only_once: false,
+        }
+    }
+
+    /// Reads a session from a file.
+    pub fn load(path: &Path) -> io::Result<Session> {
+        let contents = fs::read_to_string(path)?;
+        let mut session = Session::new("", 0);
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => return Err(invalid_line(index, line)),
+            };
+            match key {
+                "wordlist" => session.word_list = value.to_string(),
+                "words" => {
+                    session.word_count = value.parse().map_err(|_| invalid_line(index, line))?
+                }
+                "hard" => session.hard_mode = value.parse().map_err(|_| invalid_line(index, line))?,
+                "boards" => session.boards = value.parse().map_err(|_| invalid_line(index, line))?,
+                "lies" => session.lies = value.parse().map_err(|_| invalid_line(index, line))?,
+                "feedback" => {
+                    session.feedback = value.parse().map_err(|_| invalid_line(index, line))?
+                }
+                "exclude" => session.exclude = value.to_string(),
+                "correct" => session.correct = value.to_string(),
+                "incorrect" => session.incorrect.push(value.to_string()),
+                "guess" => match value.split_once(' ') {
+                    Some((guess, feedback)) => session
+                        .guesses
+                        .push((guess.to_string(), feedback.trim().to_string())),
+                    None => return Err(invalid_line(index, line)),
+                },
+                "remove" => session.removed.push(value.to_string()),
+                "query" => session.query = value.to_string(),
+                "anagram" => session.anagram = value.to_string(),
+                "only" => session.only = value.to_string(),
+                "only_once" => {
+                    session.only_once = value.parse().map_err(|_| invalid_line(index, line))?
+                }
+                _ => return Err(invalid_line(index, line)),
+            }
+        }
+        Ok(session)
+    }
+
+    /// Writes the session to a file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut contents = String::from("# wordlesolver session\n");
+        contents.push_str(&format!("wordlist: {}\n", self.word_list));
+        contents.push_str(&format!("words: {}\n", self.word_count));
+        contents.push_str(&format!("hard: {}\n", self.hard_mode));
+        contents.push_str(&format!("boards: {}\n", self.boards));
+        contents.push_str(&format!("lies: {}\n", self.lies));
+        contents.push_str(&format!("feedback: {}\n", self.feedback));
+        contents.push_str(&format!("exclude: {}\n", self.exclude));
+        contents.push_str(&format!("correct: {}\n", self.correct));
+        for incorrect in &self.incorrect {
+            contents.push_str(&format!("incorrect: {}\n", incorrect));
+        }
+        for (guess, feedback) in &self.guesses {
+            contents.push_str(&format!("guess: {} {}\n", guess, feedback));
+        }
+        for word in &self.removed {
+            contents.push_str(&format!("remove: {}\n", word));
+        }
+        if !self.query.is_empty() {
+            contents.push_str(&format!("query: {}\n", self.query));
+        }
+        if !self.anagram.is_empty() {
+            contents.push_str(&format!("anagram: {}\n", self.anagram));
+        }
+        if !self.only.is_empty() {
+            contents.push_str(&format!("only: {}\n", self.only));
+            contents.push_str(&format!("only_once: {}\n", self.only_once));
+        }
+        fs::write(path, contents)
+    }
+
+    /// Returns true if the session was saved with the same list of words.
+    pub fn same_word_list(&self, word_list: &str, word_count: usize) -> bool {
+        self.word_list == word_list && self.word_count == word_count
+    }
+
+    /// Adds letters to the list of excluded letters, skipping letters
+    /// that are already excluded.
+    pub fn add_exclude(&mut self, letters: &str) {
+        for letter in letters.chars() {
+            if !self.exclude.contains(letter) {
+                self.exclude.push(letter);
+            }
+        }
+    }
+
+    /// Adds letters in the correct position. Known letters ('.' is
+    /// unknown) replace the ones already in the session. While no letter
+    /// is known, letters of any length (e.g. for codes) replace them all.
+    ///
+    /// Returns an error, and keeps the letters, if the length is not the
+    /// length of the known letters.
+    pub fn add_correct(&mut self, letters: &str) -> Result<(), String> {
+        if self.correct.chars().all(|letter| letter == '.') {
+            self.correct = letters.to_string();
+            return Ok(());
+        }
+        if letters.chars().count() != self.correct.chars().count() {
+            return Err(format!(
+                "length of correct letters is not {}",
+                self.correct.chars().count()
+            ));
+        }
+        self.correct = self
+            .correct
+            .chars()
+            .zip(letters.chars())
+            .map(|(old, new)| if new == '.' { old } else { new })
+            .collect();
+        Ok(())
+    }
+
+    /// Returns the state of each letter, 'a' to 'z', from the guesses and
+    /// the excluded, correct and incorrect letters.
+    pub fn letter_states(&self) -> [LetterState; 26] {
+        // only colors tell what is known about each letter
+        let mut states = match self.feedback {
+            FeedbackMode::Colors => wordle::letter_states(&self.guesses),
+            _ => [LetterState::Unknown; 26],
+        };
+        for letter in self.exclude.chars() {
+            wordle::update_letter_state(&mut states, letter, LetterState::Absent);
+        }
+        for letter in self.correct.chars() {
+            wordle::update_letter_state(&mut states, letter, LetterState::Placed);
+        }
+        for incorrect in &self.incorrect {
+            for letter in incorrect.chars() {
+                wordle::update_letter_state(&mut states, letter, LetterState::Present);
+            }
+        }
+        states
+    }
+
+    /// Applies the session to the list of words.
+    ///
+    /// The list is reset first, so that only the filters and guesses
+    /// in the session are applied. Returns the error of the first filter,
+    /// or guess, that is rejected (see [`WordleWords::correct_letters`],
+    /// [`WordleWords::apply_query`] and [`WordleWords::apply_guess`]);
+    /// the other filters and guesses are still applied.
+    pub fn apply(&self, words: &mut WordleWords) -> Result<(), String> {
+        words.reset_list();
+        words.set_hard_mode(self.hard_mode);
+        words.set_lies(self.lies);
+        words.set_feedback_mode(self.feedback);
+        words.remove_letters(&self.exclude);
+        let mut result = words.correct_letters(&self.correct);
+        for incorrect in &self.incorrect {
+            result = result.and(words.incorrect_letters(incorrect));
+        }
+        if !self.query.is_empty() {
+            result = result.and(words.apply_query(&self.query));
+        }
+        if !self.anagram.is_empty() {
+            words.anagram_letters(&self.anagram);
+        }
+        if !self.only.is_empty() {
+            words.only_letters(&self.only, !self.only_once);
+        }
+        for (guess, feedback) in &self.guesses {
+            if let Err(e) = words.apply_guess(guess, feedback) {
+                result = result.and(Err(e));
+            }
+        }
+        words.remove_words(&self.removed);
+        result
+    }
+
+    /// Applies the session to a list of words that previous has already
+    /// been applied to.
+    ///
+    /// When the session only adds letters and guesses to previous, only
+    /// the new filters are applied, to the words left rather than to the
+    /// original list, which is much faster for long lists. Otherwise the
+    /// session is applied from the start. Errors are returned as by
+    /// [`Session::apply`].
+    pub fn apply_changes(&self, previous: &Session, words: &mut WordleWords) -> Result<(), String> {
+        if !self.narrows(previous) {
+            return self.apply(words);
+        }
+
+        let exclude: String = self
+            .exclude
+            .chars()
+            .filter(|&letter| !previous.exclude.contains(letter))
+            .collect();
+        if !exclude.is_empty() {
+            words.remove_letters(&exclude);
+        }
+        let mut result = Ok(());
+        if self.correct != previous.correct {
+            result = words.correct_letters(&self.correct);
+        }
+        for (index, incorrect) in self.incorrect.iter().enumerate() {
+            if previous.incorrect.get(index) != Some(incorrect) {
+                result = result.and(words.incorrect_letters(incorrect));
+            }
+        }
+        for (guess, feedback) in &self.guesses[previous.guesses.len()..] {
+            if let Err(e) = words.apply_guess(guess, feedback) {
+                result = result.and(Err(e));
+            }
+        }
+        words.remove_words(&self.removed);
+        result
+    }
+
+    // Returns true if the session has all the letters and guesses of
+    // previous, so every word it allows is also allowed by previous.
+    fn narrows(&self, previous: &Session) -> bool {
+        self.hard_mode == previous.hard_mode
+            && self.lies == previous.lies
+            && self.feedback == previous.feedback
+            && previous
+                .exclude
+                .chars()
+                .all(|letter| self.exclude.contains(letter))
+            && adds_letters(&previous.correct, &self.correct)
+            && previous.incorrect.iter().enumerate().all(|(index, old)| {
+                self.incorrect
+                    .get(index)
+                    .is_some_and(|new| adds_letters(old, new))
+            })
+            && self.guesses.starts_with(&previous.guesses)
+            && previous.removed.iter().all(|word| self.removed.contains(word))
+            && self.query == previous.query
+            && self.anagram == previous.anagram
+            && self.only == previous.only
+            && self.only_once == previous.only_once
+    }
+}
+
+// Returns true if new has the same letters as old, with some '.' (unknown)
+// letters replaced.
+fn adds_letters(old: &str, new: &str) -> bool {
+    old.len() == new.len()
+        && old
+            .chars()
+            .zip(new.chars())
+            .all(|(old, new)| old == '.' || old == new)
+}
+
+// error for a line in a session file that cannot be understood
+fn invalid_line(index: usize, line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid session line {}: {}", index + 1, line),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    #[test]
+    fn save_load_test_1() {
+        let mut session = Session::new("wordle.list", 3);
+        session.hard_mode = true;
+        session.boards = 2;
+        session.lies = 1;
+        session.feedback = FeedbackMode::Count;
+        session.add_exclude("st");
+        session.add_exclude("ta");
+        session.add_correct("b....").unwrap();
+        session.add_correct("...o.").unwrap();
+        assert!(session.add_correct("abc").is_err());
+        session.incorrect.push(String::from("d...."));
+        session.guesses.push((String::from("crane"), String::from("bybbb")));
+        session.removed.push(String::from("boxes"));
+        session.query = String::from("?r??e -s");
+        session.anagram = String::from("tacer");
+        session.only = String::from("acerti");
+        session.only_once = true;
+
+        let path = env::temp_dir().join("wordlesolver_save_load_test_1.session");
+        session.save(&path).unwrap();
+        let loaded = Session::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, session);
+        assert_eq!(loaded.exclude, "sta");
+        assert_eq!(loaded.correct, "b..o.");
+        assert!(loaded.same_word_list("wordle.list", 3));
+        assert!(!loaded.same_word_list("wordle.list", 4));
+    }
+
+    #[test]
+    fn load_test_1() {
+        let path = env::temp_dir().join("wordlesolver_load_test_1.session");
+        fs::write(&path, "wordlist: wordle.list\nboards two\n").unwrap();
+        let result = Session::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn letter_states_test_1() {
+        let mut session = Session::new("wordle.list", 0);
+        session.add_exclude("st");
+        session.add_correct("b....").unwrap();
+        session.incorrect.push(String::from("..t.."));
+        session.guesses.push((String::from("crane"), String::from("bygbb")));
+        let states = session.letter_states();
+        assert_eq!(states[(b's' - b'a') as usize], LetterState::Absent);
+        // 't' is excluded, but known to be in the word
+        assert_eq!(states[(b't' - b'a') as usize], LetterState::Present);
+        assert_eq!(states[(b'b' - b'a') as usize], LetterState::Placed);
+        assert_eq!(states[(b'r' - b'a') as usize], LetterState::Present);
+        assert_eq!(states[(b'z' - b'a') as usize], LetterState::Unknown);
+    }
+
+    #[test]
+    fn apply_changes_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+            String::from("bbbbb"),
+        ];
+        let mut list = WordleWords::new(v.clone());
+        let mut previous = Session::new("wordle.list", 4);
+        previous.incorrect.push(String::from("....."));
+        previous.apply(&mut list).unwrap();
+
+        // only adds letters and guesses, so the list is filtered further
+        let mut session = previous.clone();
+        session.add_exclude("b");
+        session.incorrect[0] = String::from("c....");
+        session.guesses.push((String::from("crane"), String::from("yygby")));
+        assert!(session.narrows(&previous));
+        session.apply_changes(&previous, &mut list).unwrap();
+        assert_eq!(list.get_word_list(), &vec![String::from("react")]);
+
+        // removing a letter starts from the original list
+        let mut wider = session.clone();
+        wider.exclude.clear();
+        wider.guesses.clear();
+        assert!(!wider.narrows(&session));
+        wider.apply_changes(&session, &mut list).unwrap();
+        assert_eq!(list.get_word_list().len(), 2);
+
+        // both give the same result as applying the session from the start
+        let mut expected = WordleWords::new(v);
+        wider.apply(&mut expected).unwrap();
+        assert_eq!(list.get_word_list(), expected.get_word_list());
+    }
+
+    #[test]
+    fn apply_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+            String::from("bbbbb"),
+        ];
+        let mut list = WordleWords::new(v);
+        let mut session = Session::new("wordle.list", 4);
+        session.add_exclude("b");
+        session.removed.push(String::from("trace"));
+        session.guesses.push((String::from("crane"), String::from("yygby")));
+        session.apply(&mut list).unwrap();
+        let result = list.get_word_list();
+        assert_eq!(result.len(), 1);
+        assert_eq!("react", result[0]);
+    }
+
+    #[test]
+    fn apply_test_2() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ];
+        let mut list = WordleWords::new(v);
+        let mut session = Session::new("wordle.list", 3);
+        session.query = String::from("+t");
+        session.guesses.push((String::from("crane"), String::from("yygby")));
+        session.apply(&mut list).unwrap();
+        assert_eq!(list.get_word_list().len(), 1);
+        // the query is kept when the guesses are removed
+        list.clear_guesses();
+        assert_eq!(list.get_word_list().len(), 2);
+
+        session.query = String::from("[ab");
+        assert!(session.apply(&mut list).is_err());
+    }
+
+    #[test]
+    fn apply_test_3() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("eerie"),
+            String::from("crane"),
+        ];
+        let mut list = WordleWords::new(v);
+        let mut session = Session::new("wordle.list", 4);
+        session.only = String::from("acerti");
+        session.apply(&mut list).unwrap();
+        assert_eq!(list.get_word_list().len(), 3);
+        session.only_once = true;
+        session.apply(&mut list).unwrap();
+        assert_eq!(list.get_word_list().len(), 2);
+
+        session.anagram = String::from("TACER");
+        session.guesses.push((String::from("react"), String::from("yyggy")));
+        session.apply(&mut list).unwrap();
+        assert_eq!(list.get_word_list(), &vec![String::from("trace")]);
+        // the letters are kept when the guesses are removed
+        list.clear_guesses();
+        assert_eq!(list.get_word_list().len(), 2);
+    }
+}
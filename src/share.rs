@@ -0,0 +1,383 @@
+// Share grids: the emoji grids players post after a game, one row of
+// colored squares per guess. With the answer known, the guesses that
+// could have given each row can be worked out.
+
+use std::collections::HashSet;
+
+use crate::wordle::{self, WordleWords};
+
+/// Reads the rows of a share grid, as 'g', 'y' and 'b' feedback.
+///
+/// Green squares (🟩, or 🟧 in high contrast mode) are 'g', yellow
+/// squares (🟨, or 🟦) are 'y' and black or white squares (⬛, ⬜) are
+/// 'b'. Lines without squares, such as the "Wordle 1,000 3/6" header, are
+/// skipped. Rows already written as 'g', 'y' and 'b' letters are read
+/// as they are.
+///
+/// # Example
+/// ```
+/// use wordlesolver::share::parse_grid;
+///
+/// let grid = "Wordle 1,000 3/6\n\n⬛🟨⬛⬛🟩\n🟩🟩⬜🟨🟩\n🟩🟩🟩🟩🟩";
+/// assert_eq!(parse_grid(grid), vec!["bybbg", "ggbyg", "ggggg"]);
+/// ```
+pub fn parse_grid(text: &str) -> Vec<String> {
+    text.lines().filter_map(parse_row).collect()
+}
+
+/// Reads several share grids, as posted one after the other. Each run of
+/// lines of squares (see [`parse_grid`]) is a grid, so grids are
+/// separated by their headers or by blank lines.
+///
+/// # Example
+/// ```
+/// use wordlesolver::share::parse_grids;
+///
+/// let text = "Wordle 1,000 2/6\n\n⬛🟨⬛⬛🟩\n🟩🟩🟩🟩🟩\n\nWordle 1,000 1/6\n\n🟩🟩🟩🟩🟩";
+/// let grids = parse_grids(text);
+/// assert_eq!(grids.len(), 2);
+/// assert_eq!(grids[1], vec!["ggggg"]);
+/// ```
+pub fn parse_grids(text: &str) -> Vec<Vec<String>> {
+    let mut grids = Vec::new();
+    let mut grid = Vec::new();
+    for line in text.lines() {
+        match parse_row(line) {
+            Some(row) => grid.push(row),
+            None if !grid.is_empty() => grids.push(std::mem::take(&mut grid)),
+            None => (),
+        }
+    }
+    if !grid.is_empty() {
+        grids.push(grid);
+    }
+    grids
+}
+
+// Returns the feedback of a line of a share grid, or None if the line is
+// not a row of squares.
+fn parse_row(line: &str) -> Option<String> {
+    let line = line.trim();
+    let row: String = line
+        .chars()
+        .filter_map(|c| match c {
+            '🟩' | '🟧' => Some('g'),
+            '🟨' | '🟦' => Some('y'),
+            '⬛' | '⬜' => Some('b'),
+            _ => None,
+        })
+        .collect();
+    if !row.is_empty() {
+        Some(row)
+    } else if !line.is_empty() && line.chars().all(|c| "gyb".contains(c)) {
+        Some(line.to_string())
+    } else {
+        None
+    }
+}
+
+/// Ranks the possible words of words as the answer of a day's grids: by
+/// the number of grids the word could have given, if it was the answer,
+/// with some word of the original list of words guessed for each row.
+/// Words that could have given the most grids are first.
+///
+/// The feedback every guess can get is worked out once for each word, so
+/// many grids are checked quickly. Words too long to do that for (more
+/// than 12 letters) are skipped, and could not have given any grid.
+///
+/// # Example
+/// ```
+/// use wordlesolver::share::infer_answers;
+/// use wordlesolver::wordle::WordleWords;
+///
+/// let v = vec![String::from("react"), String::from("crane"), String::from("boxes")];
+/// let words = WordleWords::new(v);
+/// // "crane" gets "yygby" against "react", and nothing gets it against
+/// // "crane" or "boxes"
+/// let grids = vec![vec![String::from("yygby"), String::from("ggggg")]];
+/// let result = infer_answers(&words, &grids);
+/// assert_eq!(result[0], (String::from("react"), 1));
+/// assert_eq!(result[1].1, 0);
+/// ```
+pub fn infer_answers(words: &WordleWords, grids: &[Vec<String>]) -> Vec<(String, usize)> {
+    let mut result: Vec<(String, usize)> = words
+        .get_word_list()
+        .iter()
+        .map(|answer| {
+            let patterns = match feedback_patterns(words.get_original_list(), answer) {
+                Some(patterns) => patterns,
+                None => return (answer.clone(), 0),
+            };
+            let count = grids
+                .iter()
+                .filter(|grid| {
+                    grid.iter().all(|row| {
+                        row.len() == answer.len()
+                            && patterns[wordle::feedback_index(row.as_bytes())]
+                    })
+                })
+                .count();
+            (answer.clone(), count)
+        })
+        .collect();
+    result.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    result
+}
+
+// Returns, for each feedback (indexed by feedback_index()), whether one of
+// the guesses gets it against the answer, or None if the answer is too
+// long to index its feedbacks.
+fn feedback_patterns(guesses: &[String], answer: &str) -> Option<Vec<bool>> {
+    let mut patterns = vec![false; wordle::feedback_count(answer.len())?];
+    let mut feedback = vec![b'b'; answer.len()];
+    for guess in guesses.iter().filter(|guess| guess.len() == answer.len()) {
+        wordle::score(guess.as_bytes(), answer.as_bytes(), &mut feedback);
+        patterns[wordle::feedback_index(&feedback)] = true;
+    }
+    Some(patterns)
+}
+
+/// Returns, for each row of feedback, the words of the original list of
+/// words that give that feedback when guessed against the answer.
+///
+/// With hard_mode, only guesses that are part of a game following the
+/// hard mode rules, from the first row to the last, are kept. Returns an
+/// error if a row does not have as many letters as the answer.
+///
+/// # Example
+/// ```
+/// use wordlesolver::share::reverse;
+/// use wordlesolver::wordle::WordleWords;
+///
+/// let v = vec![
+///     String::from("react"),
+///     String::from("trace"),
+///     String::from("crane"),
+///     String::from("boxes"),
+/// ];
+/// let words = WordleWords::new(v);
+/// let rows = vec![String::from("yygby"), String::from("ggggg")];
+/// let result = reverse(&words, "react", &rows, false).unwrap();
+/// assert_eq!(result[0], vec![String::from("crane")]);
+/// assert_eq!(result[1], vec![String::from("react")]);
+/// ```
+pub fn reverse(
+    words: &WordleWords,
+    answer: &str,
+    rows: &[String],
+    hard_mode: bool,
+) -> Result<Vec<Vec<String>>, String> {
+    if let Some(row) = rows.iter().find(|row| row.len() != answer.len()) {
+        return Err(format!(
+            "row {} does not have {} letters",
+            row,
+            answer.len()
+        ));
+    }
+
+    let mut feedback = vec![b'b'; answer.len()];
+    let candidates: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            words
+                .get_original_list()
+                .iter()
+                .filter(|word| {
+                    word.len() == answer.len() && {
+                        wordle::score(word.as_bytes(), answer.as_bytes(), &mut feedback);
+                        feedback == row.as_bytes()
+                    }
+                })
+                .cloned()
+                .collect()
+        })
+        .collect();
+
+    if hard_mode {
+        Ok(hard_mode_candidates(rows, candidates))
+    } else {
+        Ok(candidates)
+    }
+}
+
+// The letters a hard mode guess has to use, because an earlier guess
+// revealed them with a yellow square: the most of each letter, 'a' to 'z',
+// revealed by a single guess.
+type Revealed = [u8; 26];
+
+// Returns the letters the guess reveals with its feedback, added to
+// revealed.
+fn reveal(revealed: &Revealed, guess: &str, row: &str) -> Revealed {
+    let mut result = *revealed;
+    let guess = guess.as_bytes();
+    let row = row.as_bytes();
+    for j in 0..row.len() {
+        if row[j] == b'y' && guess[j].is_ascii_lowercase() {
+            let letter = guess[j];
+            let count = (0..row.len())
+                .filter(|&k| guess[k] == letter && row[k] != b'b')
+                .count() as u8;
+            let index = (letter - b'a') as usize;
+            result[index] = result[index].max(count);
+        }
+    }
+    result
+}
+
+// Returns true if the guess uses every revealed letter. Letters in green
+// squares are kept in place by the feedback of each row.
+fn uses_revealed(revealed: &Revealed, guess: &str) -> bool {
+    let mut counts = [0u8; 26];
+    for c in guess.bytes().filter(|c| c.is_ascii_lowercase()) {
+        counts[(c - b'a') as usize] += 1;
+    }
+    counts
+        .iter()
+        .zip(revealed)
+        .all(|(used, needed)| used >= needed)
+}
+
+// Keeps the candidates of each row that are part of a hard mode game.
+fn hard_mode_candidates(rows: &[String], candidates: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    // a green square has to stay green in every later row
+    let locked = |i: usize| {
+        rows[..i].iter().all(|previous| {
+            previous
+                .bytes()
+                .zip(rows[i].bytes())
+                .all(|(before, now)| before != b'g' || now == b'g')
+        })
+    };
+    if !(0..rows.len()).all(locked) {
+        return vec![Vec::new(); rows.len()];
+    }
+
+    // letters that have to be used before each row, for every game that
+    // gets that far
+    let mut before: Vec<HashSet<Revealed>> = vec![HashSet::from([[0u8; 26]])];
+    for (row, words) in rows.iter().zip(&candidates) {
+        let mut after = HashSet::new();
+        for revealed in before.last().unwrap() {
+            for word in words.iter().filter(|word| uses_revealed(revealed, word)) {
+                after.insert(reveal(revealed, word, row));
+            }
+        }
+        before.push(after);
+    }
+
+    // going back from the last row, the states a game can be finished
+    // from, and the guesses of each row that lead to them
+    let mut finishes: HashSet<Revealed> = before[rows.len()].clone();
+    let mut result = vec![Vec::new(); rows.len()];
+    for i in (0..rows.len()).rev() {
+        let mut kept = Vec::new();
+        let mut starts = HashSet::new();
+        for word in &candidates[i] {
+            for revealed in &before[i] {
+                if uses_revealed(revealed, word)
+                    && finishes.contains(&reveal(revealed, word, &rows[i]))
+                {
+                    if !kept.contains(word) {
+                        kept.push(word.clone());
+                    }
+                    starts.insert(*revealed);
+                }
+            }
+        }
+        result[i] = kept;
+        finishes = starts;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("crane"),
+            String::from("bored"),
+            String::from("boxes"),
+            String::from("tubes"),
+            String::from("tubas"),
+        ];
+        let words = WordleWords::new(v);
+        // each only gets a yellow 'e' or 'a' in the same square
+        let rows = vec![String::from("bbbyb"), String::from("ggggg")];
+        let result = reverse(&words, "crane", &rows, false).unwrap();
+        assert_eq!(result[0], vec!["boxes", "tubes", "tubas"]);
+        assert_eq!(result[1], vec![String::from("crane")]);
+
+        assert!(reverse(&words, "cran", &rows, false).is_err());
+    }
+
+    #[test]
+    fn hard_mode_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("crane"),
+            String::from("bored"),
+            String::from("boxes"),
+            String::from("tubes"),
+            String::from("tubas"),
+        ];
+        let words = WordleWords::new(v);
+        // "boxes" reveals a yellow 'e', so in hard mode the next guess
+        // has to use it: "tubes" can follow it, but "tubas" cannot
+        let rows = vec![
+            String::from("bbbyb"),
+            String::from("ybbyb"),
+            String::from("ggggg"),
+        ];
+        let easy = reverse(&words, "react", &rows, false).unwrap();
+        assert_eq!(easy[1], vec![String::from("tubes"), String::from("tubas")]);
+        let hard = reverse(&words, "react", &rows, true).unwrap();
+        assert_eq!(hard[0], vec![String::from("boxes")]);
+        assert_eq!(hard[1], vec![String::from("tubes")]);
+        assert_eq!(hard[2], vec![String::from("react")]);
+    }
+
+    #[test]
+    fn hard_mode_test_2() {
+        let v = vec![
+            String::from("react"),
+            String::from("crane"),
+            String::from("bored"),
+            String::from("boxes"),
+            String::from("tubes"),
+            String::from("tubas"),
+        ];
+        let words = WordleWords::new(v);
+        // a green square that is not green in a later row
+        let rows = vec![String::from("gbbbb"), String::from("bbbbb")];
+        let result = reverse(&words, "react", &rows, true).unwrap();
+        assert!(result.iter().all(|row| row.is_empty()));
+    }
+
+    #[test]
+    fn infer_answers_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("crane"),
+            String::from("bored"),
+            String::from("boxes"),
+            String::from("tubes"),
+            String::from("tubas"),
+        ];
+        let words = WordleWords::new(v);
+        let grids = vec![
+            vec![String::from("bbbyb"), String::from("ggggg")],
+            vec![String::from("ybbyb"), String::from("ggggg")],
+            vec![String::from("bbbb")],
+        ];
+        // "boxes" and "tubes" give the rows against "react", but nothing
+        // gives "ybbyb" against "crane"
+        let result = infer_answers(&words, &grids);
+        assert_eq!(result[0], (String::from("react"), 2));
+        assert_eq!(result[1], (String::from("crane"), 1));
+        assert_eq!(result.len(), 6);
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::{self, BufRead, Write};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::api;
+use crate::wordle::WordleWords;
+
+/// Answers newline delimited JSON requests read from stdin, writing one
+/// JSON response per line to stdout.
+///
+/// Requests have a `cmd` field:
+///
+/// - `{"cmd": "guess", "guess": "crane", "feedback": "bygbb"}`
+/// - `{"cmd": "candidates"}`
+/// - `{"cmd": "suggest", "n": 5}`
+/// - `{"cmd": "reset"}`
+/// - `{"cmd": "undo"}`
+///
+/// Errors are reported as `{"error": "..."}`.
+pub struct Stdio {
+    solver: WordleWords,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "cmd", rename_all = "lowercase")]
+enum Request {
+    Guess { guess: String, feedback: String },
+    Candidates,
+    Suggest {
+        #[serde(default = "default_suggestions")]
+        n: usize,
+    },
+    Reset,
+    Undo,
+}
+
+fn default_suggestions() -> usize {
+    api::DEFAULT_SUGGESTIONS
+}
+
+impl Stdio {
+    pub fn new(solver: WordleWords) -> Stdio {
+        Stdio { solver }
+    }
+
+    /// Returns the list of possible words, with the guesses received
+    /// applied.
+    pub fn into_solver(self) -> WordleWords {
+        self.solver
+    }
+
+    /// Answers requests until the end of stdin.
+    pub fn start(&mut self) {
+        let stdin = io::stdin();
+        let mut stdout = io::stdout();
+        for line in stdin.lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = self.execute(&line);
+            if writeln!(stdout, "{}", response).is_err() || stdout.flush().is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Answers one request.
+    pub fn execute(&mut self, line: &str) -> Value {
+        let request: Request = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return api::error(&format!("invalid request: {}", e)),
+        };
+        let result = match request {
+            Request::Guess { guess, feedback } => api::guess(&mut self.solver, &guess, &feedback),
+            Request::Candidates => Ok(api::candidates(&self.solver)),
+            Request::Suggest { n } => Ok(api::suggestions(&self.solver, n)),
+            Request::Reset => Ok(api::reset(&mut self.solver)),
+            Request::Undo => api::undo(&mut self.solver),
+        };
+        result.unwrap_or_else(|message| api::error(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn new_stdio() -> Stdio {
+        Stdio::new(WordleWords::new(vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ]))
+    }
+
+    #[test]
+    fn execute_test_1() {
+        let mut stdio = new_stdio();
+        let response = stdio.execute(r#"{"cmd": "guess", "guess": "crane", "feedback": "yygby"}"#);
+        assert_eq!(response, json!({ "count": 1 }));
+        let response = stdio.execute(r#"{"cmd": "candidates"}"#);
+        assert_eq!(response, json!({ "count": 1, "candidates": ["react"] }));
+        let response = stdio.execute(r#"{"cmd": "suggest", "n": 1}"#);
+        assert_eq!(response["suggestions"][0]["word"], "react");
+        let response = stdio.execute(r#"{"cmd": "undo"}"#);
+        assert_eq!(
+            response,
+            json!({ "guess": "crane", "feedback": "yygby", "count": 3 })
+        );
+        let response = stdio.execute(r#"{"cmd": "reset"}"#);
+        assert_eq!(response, json!({ "count": 3 }));
+    }
+
+    #[test]
+    fn error_test_1() {
+        let mut stdio = new_stdio();
+        let response = stdio.execute(r#"{"cmd": "undo"}"#);
+        assert_eq!(response, json!({ "error": "no guesses to undo" }));
+        let response = stdio.execute(r#"{"cmd": "jump"}"#);
+        assert!(response["error"].is_string());
+        let response = stdio.execute("guess crane yygby");
+        assert!(response["error"].is_string());
+        let response = stdio.execute(r#"{"cmd": "guess", "guess": "crane", "feedback": "y"}"#);
+        assert!(response["error"].is_string());
+    }
+}
@@ -1,28 +1,71 @@
 use cursive::{
-    event::{EventResult, Key},
-    traits::With,
-    view::{Nameable, scroll::Scroller, Scrollable},
-    views::{Dialog, EditView, LinearLayout, ListView, OnEventView, TextView},
+    align::HAlign,
+    view::{Nameable, Resizable, Scrollable},
+    views::{Dialog, EditView, LinearLayout, ListView, SelectView, TextView},
 };
 use cursive::Cursive;
 use cursive::CursiveRunnable;
 
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::GuessGrid;
+use crate::keyboard::Keyboard;
+use crate::session::Session;
+use crate::userlist::UserList;
 use crate::wordle;
 
 pub struct Tui {
     siv: CursiveRunnable,
+    // number of guess rows shown when the TUI starts
+    rows: usize,
 }
 
 struct Data {
     solver: wordle::WordleWords,
+    // list of words as read, before the banned and pinned words are applied
+    base: wordle::WordleWords,
+    // words banned or pinned by the user
+    user_list: UserList,
+    // session the TUI was started with, holding the word list details
+    session: Session,
+    // session last applied to the solver, so changes can be applied
+    // without starting from the original list
+    applied: Session,
+    // counts the updates of the word list, so that suggestions worked out
+    // for an older list are not shown
+    generation: usize,
+    // sends the word lists to work out suggestions for to the suggestions
+    // thread, with their generation
+    suggester: Sender<(usize, wordle::WordleWords)>,
 }
 
+// default file name used to save and load sessions
+const SESSION_FILE: &str = "wordle.session";
+
+// number of suggested guesses shown
+const SUGGESTIONS: usize = 10;
+
 impl Tui {
-    pub fn new(solver: wordle::WordleWords) -> Tui {
+    pub fn new(
+        mut solver: wordle::WordleWords,
+        session: Session,
+        rows: usize,
+        user_list: UserList,
+    ) -> Tui {
         let mut siv = cursive::default();
-        siv.set_user_data(Data {solver});
+        let base = solver.clone();
+        user_list.apply(&mut solver);
+        let applied = session.clone();
+        let (suggester, lists) = mpsc::channel();
+        let sink = siv.cb_sink().clone();
+        thread::spawn(move || suggest_lists(lists, sink));
+        siv.set_user_data(Data {solver, base, user_list, session, applied, generation: 0, suggester});
         Tui {
-            siv: siv,
+            siv,
+            rows: rows.max(1),
         }
     }
 
@@ -30,34 +73,17 @@ impl Tui {
         // quit by pressing q
         self.siv.add_global_callback('q', |s| s.quit());
 
-        // a scrollable view holding the current list of wordle words
+        // a scrollable view holding the current list of wordle words,
+        // selecting a word shows its details
         let wordle_list_view = Dialog::around(
-            TextView::new(
-                get_possible_words(&mut self.siv))
-                .center()
+            SelectView::<String>::new()
+                .h_align(HAlign::Center)
+                .on_submit(|s, word: &String| show_word_details(s, word))
                 .with_name("wordlelist") // wrap in a NamedView
                 .scrollable() // wrap in a ScrollView
-                .wrap_with(OnEventView::new) // wrapper for reacting to events
-                .on_pre_event_inner(Key::PageUp, |v, _| {
-                    let scroller = v.get_scroller_mut();
-                    if scroller.can_scroll_up() {
-                        scroller.scroll_up(
-                            scroller.last_outer_size().y.saturating_sub(1),
-                        );
-                    }
-                    Some(EventResult::Consumed(None))
-                })
-                .on_pre_event_inner(Key::PageDown, |v, _| {
-                    let scroller = v.get_scroller_mut();
-                    if scroller.can_scroll_down() {
-                        scroller.scroll_down(
-                            scroller.last_outer_size().y.saturating_sub(1),
-                        );
-                    }
-                    Some(EventResult::Consumed(None))
-                })
         )
             .title("Possible")
+            .with_name("possibledialog")
             ;
 
         // a view holding the current list of excluded letters
@@ -78,98 +104,187 @@ impl Tui {
             .title("Include")
             ;
 
-        // a view holding a list of incorrect letters (up to 5 entries)
-        // for use with dordle, we allow up to 8 incorrect attempts
+        // a view holding a list of incorrect letters, one entry for each
+        // guess row
+        let mut incorrect_list = ListView::new();
+        for index in 0..self.rows {
+            add_incorrect_row(&mut incorrect_list, index);
+        }
         let incorrect_letters_view = Dialog::around(
-            ListView::new()
-                .child("1", TextView::new(".....").with_name("incorrect0"))
-                .child("2", TextView::new(".....").with_name("incorrect1"))
-                .child("3", TextView::new(".....").with_name("incorrect2"))
-                .child("4", TextView::new(".....").with_name("incorrect3"))
-                .child("5", TextView::new(".....").with_name("incorrect4"))
-                .child("6", TextView::new(".....").with_name("incorrect5"))
-                .child("7", TextView::new(".....").with_name("incorrect6"))
-                .child("8", TextView::new(".....").with_name("incorrect7"))
+            incorrect_list.with_name("incorrectrows")
         )
             .title("Incorrect")
             .with_name("incorrectlist")
             ;
 
-        // hold the exclude, include and incorrect letters list in a vertical view
+        // a wordle style grid of guesses, the possible word list is
+        // updated as the guesses change
+        let guess_grid_view = Dialog::around(
+            GuessGrid::new(self.rows, 5)
+                .on_change(refresh)
+                .on_submit(refresh)
+                .with_name("guessgrid")
+        )
+            .title("Guesses")
+            ;
+
+        // hold the guesses and the exclude, include and incorrect letters
+        // list in a vertical view
         let linear_view_1 = LinearLayout::vertical()
+            .child(guess_grid_view)
             .child(exclude_letters_view)
             .child(include_letters_view)
             .child(incorrect_letters_view)
             ;
 
-        // hold the previous view and the word list view in a horizontal view
+        // the best next guesses, selecting one fills in the current row
+        // of the guesses
+        let suggestion_view = Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new("word   bits worst"))
+                .child(
+                    SelectView::<String>::new()
+                        .on_submit(|s, word: &String| {
+                            s.call_on_name("guessgrid", |view: &mut GuessGrid| {
+                                view.fill_row(word)
+                            });
+                            refresh(s);
+                            let _ = s.focus_name("guessgrid");
+                        })
+                        .with_name("suggestlist")
+                        .scrollable()
+                )
+        )
+            .title("Suggestions")
+            .with_name("suggestdialog")
+            ;
+
+        // hold the previous view, the word list view and the suggestions in
+        // a horizontal view
         let linear_view_2 = LinearLayout::horizontal()
             .child(linear_view_1)
             .child(wordle_list_view)
+            .child(suggestion_view)
+            ;
+
+        // a keyboard showing what is known about each letter
+        let keyboard_view = Dialog::around(
+            Keyboard::new()
+                .with_name("keyboard")
+        )
+            .title("Letters")
+            ;
+
+        // hold the previous view above the keyboard in a vertical view
+        let linear_view_3 = LinearLayout::vertical()
+            .child(linear_view_2)
+            .child(keyboard_view)
             ;
 
         // surround the previous view with a dialog with options to update
         // the various views
-        let dialog = Dialog::around(linear_view_2)
+        let mut dialog = Dialog::around(linear_view_3)
             .title("Wordle Words")
-            .button("Exclude", |s| update_exclude(s))
-            .button("Include", |s| update_include(s))
-            .button("Incorrect", |s| update_incorrect(s))
-            .button("Update", |mut s| {
-                let exclude_content = s.call_on_name("excludelist",|view: &mut TextView| {
-                    view.get_content()
-                })
-                    .unwrap();
-
-                let include_content = s.call_on_name("includelist", |view: &mut TextView| {
-                    view.get_content()
-                })
-                    .unwrap();
-
-                let mut vec_list: Vec<String> = Vec::new();
-                for index in 0..8 {
-                    let name = &format!("incorrect{}", index);
-                    let content = s.call_on_name(name, |view: &mut TextView| {
-                        view.get_content()
-                    })
-                        .unwrap();
-                    vec_list.push(content.source().to_string());
-                }
-
-                let data: &mut Data = s.user_data().unwrap();
-                let words: &mut wordle::WordleWords = &mut data.solver;
-
-                words.reset_list();
-                words.remove_letters(&exclude_content.source());
-                words.correct_letters(&include_content.source());
-                for word in vec_list {
-                    words.incorrect_letters(&word);
-                }
-                let new_list = get_possible_words(&mut s);
-                s.call_on_name("wordlelist", |view: &mut TextView| {
-                    view.set_content(new_list);
-                });
-            })
-            .button("Reset", |s| reset(s))
+            .button("Exclude", update_exclude)
+            .button("Include", update_include)
+            .button("Incorrect", update_incorrect)
+            .button("Rows", update_rows)
+            .button("Words", update_user_list)
+            .button("Update", update)
+            .button("Reset", reset)
+            .button("Save", save_session)
+            .button("Load", load_session)
             .button("Quit", |s| s.quit());
 
+        // switch between excluding and downweighting past answers, when
+        // there are some
+        let data: &mut Data = self.siv.user_data().unwrap();
+        if data.solver.has_past_answers() {
+            dialog.add_button("Past", toggle_past_answers);
+        }
+
         self.siv.add_layer(dialog);
 
+        // show the session the TUI was started with
+        let data: &mut Data = self.siv.user_data().unwrap();
+        let session = data.session.clone();
+        set_session(&mut self.siv, &session);
+
         self.siv.run();
     }
 }
-
-fn get_possible_words(siv: &mut Cursive) -> String {
+// pop up the details of a possible word: how likely it is to be the
+// answer, and what guessing it would tell
+fn show_word_details(siv: &mut Cursive, word: &str) {
     let data: &mut Data = siv.user_data().unwrap();
-    let words: &wordle::WordleWords = &data.solver;
+    let solver = &data.solver;
 
-    let mut display: String = String::new();
+    let mut lines: Vec<String> = Vec::new();
+    match solver.frequency(word) {
+        Some(frequency) => lines.push(format!("frequency:         {}", frequency)),
+        None => lines.push(String::from("frequency:         unknown")),
+    }
+    if let Some(answer) = solver.past_answer(word) {
+        let date = answer.date.as_deref().unwrap_or("unknown date");
+        lines.push(format!("past answer:       {}", date));
+    }
+    lines.push(format!("prior probability: {:.2}%", 100.0 * solver.prior(word)));
+    lines.push(format!("average remaining: {:.2}", solver.expected_remaining(word)));
+    lines.push(String::new());
+    lines.push(String::from("feedback if guessed:"));
+    for (feedback, count) in solver.feedback_distribution(word) {
+        lines.push(format!("{} {:5}", feedback, count));
+    }
+    lines.push(String::new());
+    lines.push(String::from("Exclude: not possible in this game"));
+    lines.push(String::from("Ban:     never possible or suggested again"));
 
-    for word in words.get_word_list() {
-        display.push_str(word);
-        display.push('\n');
+    let guess = word.to_string();
+    let remove = word.to_string();
+    let ban = word.to_string();
+    siv.add_layer(
+        Dialog::around(TextView::new(lines.join("\n")).scrollable())
+            .title(word)
+            .button("Guess This", move |s| {
+                s.pop_layer();
+                s.call_on_name("guessgrid", |view: &mut GuessGrid| {
+                    view.fill_row(&guess)
+                });
+                refresh(s);
+                let _ = s.focus_name("guessgrid");
+            })
+            .button("Exclude This Word", move |s| {
+                s.pop_layer();
+                let data: &mut Data = s.user_data().unwrap();
+                data.session.removed.push(remove.clone());
+                refresh(s);
+            })
+            .button("Ban", move |s| {
+                s.pop_layer();
+                let data: &mut Data = s.user_data().unwrap();
+                data.user_list.ban(&ban);
+                apply_user_list(s);
+            })
+            .button("Close", |s| {
+                s.pop_layer();
+            })
+    );
+}
+
+// switch between removing past answers from the possible words and
+// keeping them as less likely answers, then update the possible word list
+fn toggle_past_answers(siv: &mut Cursive) {
+    let data: &mut Data = siv.user_data().unwrap();
+    let downweight = !data.solver.is_downweight_past();
+    data.base.set_downweight_past(downweight);
+    data.solver.set_downweight_past(downweight);
+
+    update(siv);
+    if downweight {
+        siv.add_layer(Dialog::info("Past answers are now less likely answers"));
+    } else {
+        siv.add_layer(Dialog::info("Past answers are now excluded"));
     }
-    display
 }
 
 // display the excluded letters in a pop up for editing
@@ -188,22 +303,24 @@ fn update_exclude(siv: &mut Cursive) {
             .content(
                 EditView::new()
                     .content(curr_content.source())
+                    .on_edit(|s, content, _| {
+                        // update the exclude list as letters are typed
+                        s.call_on_name("excludelist", |view: &mut TextView| {
+                            view.set_content(content)
+                        });
+                        refresh(s);
+                    })
                     .with_name("new_exclude_list")
             )
             .button("Done", |s| {
-                // get the new excluded letters
-                let content = s
-                    .call_on_name("new_exclude_list", |view: &mut EditView| view.get_content())
-                    .unwrap();
-                // println!("new content: {}", content);
-
-                // update the exclude list
-                s.call_on_name("excludelist", |view: &mut TextView| {
-                    view.set_content(&*content)
-                });
                 s.pop_layer();
             })
-            .button("Cancel", |s| {
+            .button("Cancel", move |s| {
+                // put back the previous exclude list
+                s.call_on_name("excludelist", |view: &mut TextView| {
+                    view.set_content(curr_content.source())
+                });
+                refresh(s);
                 s.pop_layer();
             })
     );
@@ -226,6 +343,15 @@ fn update_include(siv: &mut Cursive) {
                 EditView::new()
                     .content(curr_content.source())
                     .max_content_width(5)
+                    .on_edit(|s, content, _| {
+                        // update the include list once all five letters are typed
+                        if content.len() == 5 {
+                            s.call_on_name("includelist", |view: &mut TextView| {
+                                view.set_content(content)
+                            });
+                            refresh(s);
+                        }
+                    })
                     .with_name("new_include_list")
             )
             .button("Done", |s| {
@@ -239,9 +365,15 @@ fn update_include(siv: &mut Cursive) {
                 s.call_on_name("includelist", |view: &mut TextView| {
                     view.set_content(&*content)
                 });
+                refresh(s);
                 s.pop_layer();
             })
-            .button("Cancel", |s| {
+            .button("Cancel", move |s| {
+                // put back the previous include list
+                s.call_on_name("includelist", |view: &mut TextView| {
+                    view.set_content(curr_content.source())
+                });
+                refresh(s);
                 s.pop_layer();
             })
     );
@@ -250,7 +382,7 @@ fn update_include(siv: &mut Cursive) {
 // display the list of incorrect letters in a pop up for editing
 fn update_incorrect(siv: &mut Cursive) {
     let mut vec_list: Vec<String> = Vec::new();
-    for index in 0..8 {
+    for index in 0..row_count(siv) {
         let name = &format!("incorrect{}", index);
         let content = siv.call_on_name(name, |view: &mut TextView| {
             view.get_content()
@@ -262,17 +394,25 @@ fn update_incorrect(siv: &mut Cursive) {
 
     // create a new list of editviews with the current incorrect items
     let mut listview = ListView::new();
-    let mut index = 0;
-    for item in vec_list {
+    for (index, item) in vec_list.iter().enumerate() {
         let new_name = &format!("new_incorrect{}", index);
         listview.add_child(
             &format!("{}", index + 1),
             EditView::new()
                 .content(item)
                 .max_content_width(5)
+                .on_edit(move |s, content, _| {
+                    // update the incorrect list once all five letters are typed
+                    if content.len() == 5 {
+                        let name = &format!("incorrect{}", index);
+                        s.call_on_name(name, |view: &mut TextView| {
+                            view.set_content(content)
+                        });
+                        refresh(s);
+                    }
+                })
                 .with_name(new_name)
         );
-        index += 1;
     }
     // listview.with_name("new_incorrect_list");
 
@@ -283,7 +423,7 @@ fn update_incorrect(siv: &mut Cursive) {
                 listview.with_name("new_incorrect_list")
             )
             .button("Done", |s| {
-                for index in 0..8 {
+                for index in 0..row_count(s) {
                     let new_name = &format!("new_incorrect{}", index);
                     let old_name = &format!("incorrect{}", index);
                     // get new content
@@ -295,20 +435,280 @@ fn update_incorrect(siv: &mut Cursive) {
                         view.set_content(&*new_content)
                     });
                 }
+                refresh(s);
                 s.pop_layer();
             })
-            .button("Cancel", |s| {
+            .button("Cancel", move |s| {
+                // put back the previous incorrect list
+                for (index, item) in vec_list.iter().enumerate() {
+                    let name = &format!("incorrect{}", index);
+                    s.call_on_name(name, |view: &mut TextView| {
+                        view.set_content(item)
+                    });
+                }
+                refresh(s);
                 s.pop_layer();
             })
     );
 }
 
-fn reset(siv: &mut Cursive) {
-    // reset the list of words
+// update the possible word list from the guesses and the exclude, include
+// and incorrect letters, starting from the original list
+fn update(siv: &mut Cursive) {
+    let session = get_session(siv);
+
+    let data: &mut Data = siv.user_data().unwrap();
+    let result = session.apply(&mut data.solver);
+    data.applied = session.clone();
+
+    show_possible_words(siv, &session);
+    if let Err(e) = result {
+        siv.add_layer(Dialog::info(e));
+    }
+}
+
+// update the possible word list after the guesses or the exclude, include
+// or incorrect letters changed. When letters or guesses were only added,
+// the words left are filtered rather than the original list.
+fn refresh(siv: &mut Cursive) {
+    let session = get_session(siv);
+
+    let data: &mut Data = siv.user_data().unwrap();
+    let result = session.apply_changes(&data.applied, &mut data.solver);
+    data.applied = session.clone();
+
+    show_possible_words(siv, &session);
+    if let Err(e) = result {
+        siv.add_layer(Dialog::info(e));
+    }
+}
+
+// show the possible word list, the number of words and the keyboard
+fn show_possible_words(siv: &mut Cursive, session: &Session) {
+    let data: &mut Data = siv.user_data().unwrap();
+    let words = data.solver.get_word_list().clone();
+    let count = words.len();
+
+    siv.call_on_name("wordlelist", |view: &mut SelectView<String>| {
+        view.clear();
+        view.add_all_str(words);
+    });
+    siv.call_on_name("possibledialog", |view: &mut Dialog| {
+        view.set_title(format!("Possible ({})", count));
+    });
+    siv.call_on_name("keyboard", |view: &mut Keyboard| {
+        view.set_states(session.letter_states())
+    });
+    update_suggestions(siv);
+}
+
+// work out the suggestions for the possible word list in the background,
+// so the TUI does not freeze on long lists, then show them
+fn update_suggestions(siv: &mut Cursive) {
+    let data: &mut Data = siv.user_data().unwrap();
+    data.generation += 1;
+    // sending only fails once the suggestions thread has stopped
+    let _ = data.suggester.send((data.generation, data.solver.clone()));
+
+    siv.call_on_name("suggestdialog", |view: &mut Dialog| {
+        view.set_title("Suggestions (working)");
+    });
+}
+
+// Works out the suggestions for the word lists sent by
+// update_suggestions(), one at a time, and sends them to the TUI. Only
+// the latest list waiting is worked out, so while letters are typed
+// there is at most one list being worked out. Stops when the TUI quits.
+fn suggest_lists(lists: Receiver<(usize, wordle::WordleWords)>, sink: cursive::CbSink) {
+    while let Ok(mut latest) = lists.recv() {
+        // wait a little, as another letter may be typed straight away
+        thread::sleep(Duration::from_millis(100));
+        while let Ok(list) = lists.try_recv() {
+            latest = list;
+        }
+        let (generation, solver) = latest;
+        let suggestions = solver.suggest(SUGGESTIONS);
+        let show = Box::new(move |s: &mut Cursive| show_suggestions(s, generation, suggestions));
+        if sink.send(show).is_err() {
+            return;
+        }
+    }
+}
+
+// show the suggestions, unless the word list changed since they were
+// worked out
+fn show_suggestions(siv: &mut Cursive, generation: usize, suggestions: Vec<wordle::Suggestion>) {
+    let data: &mut Data = siv.user_data().unwrap();
+    if data.generation != generation {
+        return;
+    }
+
+    siv.call_on_name("suggestlist", |view: &mut SelectView<String>| {
+        view.clear();
+        for s in suggestions {
+            // '*' marks the possible words
+            let label = format!(
+                "{} {:5.2} {:5}{}",
+                s.word,
+                s.information,
+                s.worst_case,
+                if s.possible { " *" } else { "" }
+            );
+            view.add_item(label, s.word);
+        }
+    });
+    siv.call_on_name("suggestdialog", |view: &mut Dialog| {
+        view.set_title("Suggestions");
+    });
+}
+
+// build a session from the guesses and the exclude, include and incorrect
+// letters
+fn get_session(siv: &mut Cursive) -> Session {
+    let exclude_content = siv.call_on_name("excludelist", |view: &mut TextView| {
+        view.get_content()
+    })
+        .unwrap();
+
+    let include_content = siv.call_on_name("includelist", |view: &mut TextView| {
+        view.get_content()
+    })
+        .unwrap();
+
+    let mut vec_list: Vec<String> = Vec::new();
+    for index in 0..row_count(siv) {
+        let name = &format!("incorrect{}", index);
+        let content = siv.call_on_name(name, |view: &mut TextView| {
+            view.get_content()
+        })
+            .unwrap();
+        vec_list.push(content.source().to_string());
+    }
+
+    let guesses = siv.call_on_name("guessgrid", |view: &mut GuessGrid| {
+        view.get_guesses()
+    })
+        .unwrap();
+
     let data: &mut Data = siv.user_data().unwrap();
-    let words: &mut wordle::WordleWords = &mut data.solver;
-    words.reset_list();
+    let mut session = data.session.clone();
+    session.exclude = exclude_content.source().to_string();
+    session.correct = include_content.source().to_string();
+    session.incorrect = vec_list;
+    session.guesses = guesses;
+    session.hard_mode = data.solver.is_hard_mode();
+    session
+}
+
+// show a session in the guesses and the exclude, include and incorrect
+// views and update the possible word list
+fn set_session(siv: &mut Cursive, session: &Session) {
+    // add rows if the session has more guesses or incorrect letters
+    let rows = session.guesses.len().max(session.incorrect.len());
+    if rows > row_count(siv) {
+        set_rows(siv, rows);
+    }
+
+    siv.call_on_name("guessgrid", |view: &mut GuessGrid| {
+        view.set_guesses(&session.guesses)
+    });
+    siv.call_on_name("excludelist", |view: &mut TextView| {
+        view.set_content(session.exclude.as_str())
+    });
+    siv.call_on_name("includelist", |view: &mut TextView| {
+        view.set_content(session.correct.as_str())
+    });
+    for index in 0..row_count(siv) {
+        let name = &format!("incorrect{}", index);
+        let content = session.incorrect.get(index).map_or(".....", |s| s.as_str());
+        siv.call_on_name(name, |view: &mut TextView| {
+            view.set_content(content)
+        });
+    }
+
+    let data: &mut Data = siv.user_data().unwrap();
+    data.session.removed = session.removed.clone();
+    let result = session.apply(&mut data.solver);
+    data.applied = session.clone();
+
+    show_possible_words(siv, session);
+    if let Err(e) = result {
+        siv.add_layer(Dialog::info(e));
+    }
+}
+
+// pop up an editview asking for the session file name, then call action
+// with the file name
+fn ask_session_file<F>(siv: &mut Cursive, title: &str, action: F)
+where
+    F: Fn(&mut Cursive, &str) + 'static,
+{
+    siv.add_layer(
+        Dialog::new()
+            .title(title)
+            .content(
+                EditView::new()
+                    .content(SESSION_FILE)
+                    .with_name("session_file")
+            )
+            .button("Done", move |s| {
+                let file = s
+                    .call_on_name("session_file", |view: &mut EditView| view.get_content())
+                    .unwrap();
+                s.pop_layer();
+                action(s, &file);
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            })
+    );
+}
+
+// save the exclude, include and incorrect letters and the guesses to a file
+fn save_session(siv: &mut Cursive) {
+    ask_session_file(siv, "Save Session", |s, file| {
+        let session = get_session(s);
+        if let Err(e) = session.save(Path::new(file)) {
+            s.add_layer(Dialog::info(format!("Unable to save {}: {}", file, e)));
+        }
+    });
+}
+
+// load the exclude, include and incorrect letters and the guesses from a
+// file
+fn load_session(siv: &mut Cursive) {
+    ask_session_file(siv, "Load Session", |s, file| {
+        match Session::load(Path::new(file)) {
+            Ok(ref session) if session.feedback != wordle::FeedbackMode::Colors => {
+                s.add_layer(Dialog::info("The TUI only supports colors feedback"));
+            }
+            Ok(mut session) => {
+                let data: &mut Data = s.user_data().unwrap();
+                if !session.same_word_list(&data.session.word_list, data.session.word_count) {
+                    s.add_layer(Dialog::info(format!(
+                        "Session was saved with {} ({} words)",
+                        session.word_list, session.word_count
+                    )));
+                }
+                // keep the details of the list of words actually loaded
+                let data: &mut Data = s.user_data().unwrap();
+                session.word_list = data.session.word_list.clone();
+                session.word_count = data.session.word_count;
+                data.session.boards = session.boards;
+                set_session(s, &session);
+            }
+            Err(e) => {
+                s.add_layer(Dialog::info(format!("Unable to load {}: {}", file, e)));
+            }
+        }
+    });
+}
 
+fn reset(siv: &mut Cursive) {
+    // reset the guesses
+    siv.call_on_name("guessgrid", |view: &mut GuessGrid| {
+        view.set_guesses(&[])
+    });
     // reset the exclude list
     siv.call_on_name("excludelist", |view: &mut TextView| {
         view.set_content("")
@@ -318,15 +718,127 @@ fn reset(siv: &mut Cursive) {
         view.set_content(".....")
     });
     // reset the incorrect list
-    for index in 0..8 {
+    for index in 0..row_count(siv) {
         let name = &format!("incorrect{}", index);
         siv.call_on_name(name, |view: &mut TextView| {
             view.set_content(".....")
         });
     }
-    // undate the possible word list
-    let list = get_possible_words(siv);
-    siv.call_on_name("wordlelist", |view: &mut TextView| {
-        view.set_content(list);
+    // put back the words excluded by hand
+    let data: &mut Data = siv.user_data().unwrap();
+    data.session.removed.clear();
+    // reset and update the possible word list
+    update(siv);
+}
+
+// number of guess rows, and of incorrect letters rows
+fn row_count(siv: &mut Cursive) -> usize {
+    siv.call_on_name("guessgrid", |view: &mut GuessGrid| view.rows())
+        .unwrap()
+}
+
+// add a row of incorrect letters to the list
+fn add_incorrect_row(list: &mut ListView, index: usize) {
+    list.add_child(
+        &format!("{}", index + 1),
+        TextView::new(".....").with_name(format!("incorrect{}", index)),
+    );
+}
+
+// change the number of guess rows and incorrect letters rows, then update
+// the possible word list, as removed rows may allow more words
+fn set_rows(siv: &mut Cursive, rows: usize) {
+    let rows = rows.max(1);
+    siv.call_on_name("guessgrid", |view: &mut GuessGrid| {
+        view.set_rows(rows)
+    });
+    siv.call_on_name("incorrectrows", |view: &mut ListView| {
+        while view.len() < rows {
+            let index = view.len();
+            add_incorrect_row(view, index);
+        }
+        while view.len() > rows {
+            view.remove_child(view.len() - 1);
+        }
     });
+    update(siv);
+}
+
+// pop up an editview asking for the number of guess rows
+fn update_rows(siv: &mut Cursive) {
+    let rows = row_count(siv);
+    siv.add_layer(
+        Dialog::new()
+            .title("Number of Rows")
+            .content(
+                EditView::new()
+                    .content(rows.to_string())
+                    .with_name("new_rows")
+            )
+            .button("Done", |s| {
+                let content = s
+                    .call_on_name("new_rows", |view: &mut EditView| view.get_content())
+                    .unwrap();
+                s.pop_layer();
+                match content.trim().parse::<usize>() {
+                    Ok(rows) if rows > 0 => set_rows(s, rows),
+                    _ => s.add_layer(Dialog::info(format!("{} is not a number of rows", content))),
+                }
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            })
+    );
+}
+
+// display the banned and pinned words in a pop up for editing
+fn update_user_list(siv: &mut Cursive) {
+    let data: &mut Data = siv.user_data().unwrap();
+    let banned = data.user_list.banned.join(" ");
+    let pinned = data.user_list.pinned.join(" ");
+
+    siv.add_layer(
+        Dialog::new()
+            .title("Banned and Pinned Words")
+            .content(
+                ListView::new()
+                    .child("Banned", EditView::new().content(banned).with_name("new_banned"))
+                    .child("Pinned", EditView::new().content(pinned).with_name("new_pinned"))
+                    .min_width(40)
+            )
+            .button("Done", |s| {
+                let banned = s
+                    .call_on_name("new_banned", |view: &mut EditView| view.get_content())
+                    .unwrap();
+                let pinned = s
+                    .call_on_name("new_pinned", |view: &mut EditView| view.get_content())
+                    .unwrap();
+                s.pop_layer();
+
+                let data: &mut Data = s.user_data().unwrap();
+                data.user_list.banned.clear();
+                data.user_list.pinned.clear();
+                banned.split_whitespace().for_each(|word| data.user_list.ban(word));
+                pinned.split_whitespace().for_each(|word| data.user_list.pin(word));
+                apply_user_list(s);
+            })
+            .button("Cancel", |s| {
+                s.pop_layer();
+            })
+    );
+}
+
+// save the banned and pinned words, then apply them to the list of words
+// as read and update the possible word list
+fn apply_user_list(siv: &mut Cursive) {
+    let data: &mut Data = siv.user_data().unwrap();
+    data.solver = data.base.clone();
+    data.user_list.apply(&mut data.solver);
+    let saved = data.user_list.save();
+    let path = data.user_list.path.display().to_string();
+
+    update(siv);
+    if let Err(e) = saved {
+        siv.add_layer(Dialog::info(format!("Unable to save {}: {}", path, e)));
+    }
 }
@@ -0,0 +1,171 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::wordle::WordleWords;
+
+// name of the file holding the user's lists, in the home directory
+const USER_FILE: &str = ".wordlesolver.words";
+
+/// Words banned from, or pinned to, the list of words by the user. They
+/// are kept in a small file, applied whenever the list of words is read.
+///
+/// The file has one `ban: word` or `pin: word` entry per line, for
+/// example:
+///
+/// ```text
+/// # wordlesolver banned and pinned words
+/// ban: boxes
+/// pin: crane
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserList {
+    /// file the lists are read from and saved to
+    pub path: PathBuf,
+    /// words that are never possible answers, nor suggested as guesses,
+    /// e.g. recent answers or words the game rejects
+    pub banned: Vec<String>,
+    /// words that are always in the list, even if the word list does not
+    /// have them
+    pub pinned: Vec<String>,
+}
+
+impl UserList {
+    /// Returns empty lists, saved to path.
+    pub fn new(path: &Path) -> UserList {
+        UserList {
+            path: path.to_path_buf(),
+            banned: Vec::new(),
+            pinned: Vec::new(),
+        }
+    }
+
+    /// Returns the default file for the lists, in the home directory (or
+    /// the current directory if there is no home directory).
+    pub fn default_path() -> PathBuf {
+        match env::var_os("HOME") {
+            Some(home) => Path::new(&home).join(USER_FILE),
+            None => PathBuf::from(USER_FILE),
+        }
+    }
+
+    /// Reads the lists from a file. A file that does not exist gives empty
+    /// lists.
+    pub fn load(path: &Path) -> io::Result<UserList> {
+        let mut list = UserList::new(path);
+        if !path.exists() {
+            return Ok(list);
+        }
+
+        let contents = fs::read_to_string(path)?;
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once(':') {
+                Some(("ban", word)) => list.ban(word.trim()),
+                Some(("pin", word)) => list.pin(word.trim()),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid line {} in {}: {}", index + 1, path.display(), line),
+                    ))
+                }
+            }
+        }
+        Ok(list)
+    }
+
+    /// Writes the lists to their file.
+    pub fn save(&self) -> io::Result<()> {
+        let mut contents = String::from("# wordlesolver banned and pinned words\n");
+        for word in &self.banned {
+            contents.push_str(&format!("ban: {}\n", word));
+        }
+        for word in &self.pinned {
+            contents.push_str(&format!("pin: {}\n", word));
+        }
+        fs::write(&self.path, contents)
+    }
+
+    /// Bans a word, unpinning it if it was pinned.
+    pub fn ban(&mut self, word: &str) {
+        self.pinned.retain(|w| w != word);
+        if !self.banned.iter().any(|w| w == word) {
+            self.banned.push(word.to_string());
+        }
+    }
+
+    /// Removes a word from the banned words.
+    pub fn unban(&mut self, word: &str) {
+        self.banned.retain(|w| w != word);
+    }
+
+    /// Pins a word, unbanning it if it was banned.
+    pub fn pin(&mut self, word: &str) {
+        self.banned.retain(|w| w != word);
+        if !self.pinned.iter().any(|w| w == word) {
+            self.pinned.push(word.to_string());
+        }
+    }
+
+    /// Removes a word from the pinned words.
+    pub fn unpin(&mut self, word: &str) {
+        self.pinned.retain(|w| w != word);
+    }
+
+    /// Removes the banned words from the possible words, and adds the
+    /// pinned words to the list of words.
+    pub fn apply(&self, words: &mut WordleWords) {
+        words.ban_words(&self.banned);
+        words.pin_words(&self.pinned);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_load_test_1() {
+        let path = env::temp_dir().join("wordlesolver_user_list_test_1.words");
+        let mut list = UserList::new(&path);
+        list.ban("boxes");
+        list.ban("crane");
+        list.pin("crane");
+        list.pin("trace");
+        list.unpin("trace");
+        assert_eq!(list.banned, vec![String::from("boxes")]);
+        assert_eq!(list.pinned, vec![String::from("crane")]);
+
+        list.save().unwrap();
+        let loaded = UserList::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, list);
+
+        // a missing file is empty lists
+        assert!(UserList::load(&path).unwrap().banned.is_empty());
+    }
+
+    #[test]
+    fn apply_test_1() {
+        let v = vec![String::from("react"), String::from("boxes")];
+        let mut words = WordleWords::new(v);
+        let mut list = UserList::new(Path::new("unused"));
+        list.ban("boxes");
+        list.pin("crane");
+        list.apply(&mut words);
+        assert_eq!(
+            words.get_word_list(),
+            &vec![String::from("react"), String::from("crane")]
+        );
+        // banned words can still be guessed, but are not suggested
+        assert!(words.get_original_list().contains(&String::from("boxes")));
+        assert!(words.suggest(3).iter().all(|s| s.word != "boxes"));
+        words.apply_guess("boxes", "bbbyb").unwrap();
+        words.reset_list();
+        assert_eq!(words.get_word_list().len(), 2);
+    }
+}
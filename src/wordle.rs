@@ -1,9 +1,645 @@
+use serde::Serialize;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
 /// Structure to hold list of possible Wordle words.
+#[derive(Clone)]
 pub struct WordleWords {
     // original list of words
     original_list: Vec<String>,
     // latest list of words after filtering out invalid words based on provided patterns
     current_list: Vec<String>,
+    // list of words with the filters applied but not the guesses, to go
+    // back to when guesses are removed
+    base_list: Vec<String>,
+    // guesses (and their feedback) that have been applied to the list
+    guesses: Vec<(String, String)>,
+    // words that can be guessed, but are never possible words
+    banned: HashSet<String>,
+    // whether guesses have to follow the hard mode rules
+    hard_mode: bool,
+    // how often each word is used, for the words where it is known
+    frequencies: HashMap<String, f64>,
+    // words that were the answer before, by word
+    past_answers: HashMap<String, PastAnswer>,
+    // whether past answers are less likely answers, rather than not
+    // possible at all
+    downweight_past: bool,
+    // number of tiles of each feedback that can be wrong (e.g. 1 for
+    // Fibble)
+    lies: usize,
+    // how the game scores a guess
+    mode: FeedbackMode,
+}
+
+// how much less likely a past answer is to be the answer again, when past
+// answers are downweighted
+const PAST_ANSWER_WEIGHT: f64 = 0.05;
+
+/// Most codes [`generate_codes`] returns. Suggestions score every code
+/// against every other, so more would take too long.
+pub const MAX_CODES: usize = 100_000;
+
+/// How the game scores a guess against the answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedbackMode {
+    /// a 'g', 'y' or 'b' color for each letter, as in Wordle (e.g.
+    /// "bygbb")
+    #[default]
+    Colors,
+    /// the number of letters the guess has in common with the answer, as
+    /// in Jotto (e.g. "2")
+    Count,
+    /// the number of black pegs (right symbol in the right position) and
+    /// white pegs (right symbol in another position), as in Mastermind or
+    /// Bulls and Cows (e.g. "1b2w")
+    Pegs,
+}
+
+impl FeedbackMode {
+    /// Returns the feedback for a guess against an answer.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::FeedbackMode;
+    ///
+    /// assert_eq!(FeedbackMode::Colors.feedback("crane", "react"), "yygby");
+    /// assert_eq!(FeedbackMode::Count.feedback("crane", "react"), "4");
+    /// assert_eq!(FeedbackMode::Pegs.feedback("1234", "1325"), "1b2w");
+    /// ```
+    pub fn feedback(self, guess: &str, answer: &str) -> String {
+        match self {
+            FeedbackMode::Colors => get_feedback(guess, answer),
+            FeedbackMode::Count => common_letters(guess, answer).to_string(),
+            FeedbackMode::Pegs => {
+                let (black, white) = pegs(guess, answer);
+                format!("{}b{}w", black, white)
+            }
+        }
+    }
+
+    /// Returns true if the feedback can be given to the guess.
+    pub fn is_valid(self, guess: &str, feedback: &str) -> bool {
+        match self {
+            FeedbackMode::Colors => is_valid_feedback(guess, feedback),
+            FeedbackMode::Count => feedback
+                .parse::<usize>()
+                .is_ok_and(|count| count <= guess.len()),
+            FeedbackMode::Pegs => {
+                parse_pegs(feedback).is_some_and(|(black, white)| black + white <= guess.len())
+            }
+        }
+    }
+
+    // Returns how far the feedback is from the feedback the guess would
+    // get if word was the answer: the number of tiles that differ, or how
+    // much the count (or the pegs) are off by.
+    fn errors(self, guess: &str, feedback: &str, word: &str) -> usize {
+        match self {
+            FeedbackMode::Colors => get_feedback(guess, word)
+                .chars()
+                .zip(feedback.chars())
+                .filter(|(expected, given)| expected != given)
+                .count(),
+            FeedbackMode::Count => {
+                let given = feedback.parse::<usize>().unwrap_or(0);
+                common_letters(guess, word).abs_diff(given)
+            }
+            FeedbackMode::Pegs => {
+                let (given_black, given_white) = parse_pegs(feedback).unwrap_or((0, 0));
+                let (black, white) = pegs(guess, word);
+                black.abs_diff(given_black) + white.abs_diff(given_white)
+            }
+        }
+    }
+}
+
+impl FromStr for FeedbackMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<FeedbackMode, String> {
+        match s {
+            "colors" | "wordle" => Ok(FeedbackMode::Colors),
+            "count" | "jotto" => Ok(FeedbackMode::Count),
+            "pegs" | "mastermind" => Ok(FeedbackMode::Pegs),
+            _ => Err(format!("{} is not a feedback mode (colors, count or pegs)", s)),
+        }
+    }
+}
+
+impl fmt::Display for FeedbackMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedbackMode::Colors => write!(f, "colors"),
+            FeedbackMode::Count => write!(f, "count"),
+            FeedbackMode::Pegs => write!(f, "pegs"),
+        }
+    }
+}
+
+/// A word that was the answer before, and the date (if known).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PastAnswer {
+    /// the answer
+    pub word: String,
+    /// the date it was the answer, as written in the file
+    pub date: Option<String>,
+}
+
+/// A crossword style query over the list of words, made of terms
+/// separated by spaces:
+///
+/// - a pattern with one entry per letter of the word: a letter, '?' (or
+///   '.') for any letter, `[bcd]` for one of the letters, or `[^bcd]`
+///   for any other letter, e.g. `?a?e?` or `[bc]r??[^s]`
+/// - `+letters`, the word contains all of the letters
+/// - `-letters`, the word contains none of the letters
+/// - `=letters`, the word only uses these letters
+///
+/// A term without a prefix is always a pattern, so `e` is a pattern for
+/// words of one letter, not the letter 'e' (that is `+e`).
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::Query;
+///
+/// let query = Query::parse("[bc]?a?e +n").unwrap();
+/// assert!(query.matches("crane"));
+/// assert!(!query.matches("brake"));
+/// assert!(!query.matches("chase"));
+/// assert!(Query::parse("=aecnr").unwrap().matches("crane"));
+/// assert!(Query::parse("[ab").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Query {
+    // for each pattern, the letters allowed at each position and whether
+    // they are the letters not allowed instead
+    patterns: Vec<Vec<(String, bool)>>,
+    // letters the word has to contain
+    contains: String,
+    // letters the word cannot contain
+    excludes: String,
+    // letters the word can only be made of, if any were given
+    only: Option<String>,
+}
+
+impl Query {
+    /// Reads a query, returning an error if a term cannot be read.
+    pub fn parse(text: &str) -> Result<Query, String> {
+        let mut query = Query::default();
+        for term in text.to_lowercase().split_whitespace() {
+            if let Some(letters) = term.strip_prefix('+') {
+                query.contains.push_str(letters);
+            } else if let Some(letters) = term.strip_prefix('-') {
+                query.excludes.push_str(letters);
+            } else if let Some(letters) = term.strip_prefix('=') {
+                query.only.get_or_insert_with(String::new).push_str(letters);
+            } else {
+                query.patterns.push(parse_pattern(term)?);
+            }
+        }
+        Ok(query)
+    }
+
+    /// Returns true if the word matches every term of the query.
+    pub fn matches(&self, word: &str) -> bool {
+        let positions_match = |pattern: &Vec<(String, bool)>| {
+            pattern.len() == word.chars().count()
+                && pattern
+                    .iter()
+                    .zip(word.chars())
+                    .all(|((letters, negated), c)| letters.contains(c) != *negated)
+        };
+        self.patterns.iter().all(positions_match)
+            && self.contains.chars().all(|c| word.contains(c))
+            && !word.chars().any(|c| self.excludes.contains(c))
+            && self
+                .only
+                .as_ref()
+                .is_none_or(|only| uses_only_letters(word, only, true))
+    }
+}
+
+// Reads a pattern of a query, as the letters allowed (or not allowed) at
+// each position. Any letter is the empty list of letters not allowed.
+fn parse_pattern(term: &str) -> Result<Vec<(String, bool)>, String> {
+    let mut pattern = Vec::new();
+    let mut chars = term.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '?' | '.' => pattern.push((String::new(), true)),
+            '[' => {
+                let mut letters = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        closed = true;
+                        break;
+                    }
+                    letters.push(c);
+                }
+                if !closed {
+                    return Err(format!("missing ']' in {}", term));
+                }
+                match letters.strip_prefix('^') {
+                    Some(letters) => pattern.push((letters.to_string(), true)),
+                    None => pattern.push((letters, false)),
+                }
+            }
+            c if c.is_alphanumeric() => pattern.push((c.to_string(), false)),
+            c => return Err(format!("unexpected '{}' in {}", c, term)),
+        }
+    }
+    Ok(pattern)
+}
+
+/// A possible next guess, and how much it is expected to narrow down the
+/// list of possible words.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    /// the word to guess
+    pub word: String,
+    /// expected information, in bits, from the feedback to the guess
+    pub information: f64,
+    /// most possible words that can be left after the guess
+    pub worst_case: usize,
+    /// true if the word is one of the possible words
+    pub possible: bool,
+}
+
+/// What is known about a letter, like the colors of the keyboard in
+/// Wordle. Later states are better known, so the most known state of a
+/// letter is the maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LetterState {
+    /// the letter has not been guessed
+    Unknown,
+    /// the letter is not in the word
+    Absent,
+    /// the letter is in the word, position unknown
+    Present,
+    /// the letter is in the word, in a known position
+    Placed,
+}
+
+/// Returns the state of each letter, 'a' to 'z', from guesses and their
+/// feedback.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::{letter_states, LetterState};
+///
+/// let guesses = vec![(String::from("crane"), String::from("bygbb"))];
+/// let states = letter_states(&guesses);
+/// assert_eq!(states[0], LetterState::Placed); // 'a'
+/// assert_eq!(states[2], LetterState::Absent); // 'c'
+/// assert_eq!(states[17], LetterState::Present); // 'r'
+/// assert_eq!(states[25], LetterState::Unknown); // 'z'
+/// ```
+pub fn letter_states(guesses: &[(String, String)]) -> [LetterState; 26] {
+    let mut states = [LetterState::Unknown; 26];
+    for (guess, feedback) in guesses {
+        for (letter, color) in guess.chars().zip(feedback.chars()) {
+            let state = match color {
+                'g' => LetterState::Placed,
+                'y' => LetterState::Present,
+                _ => LetterState::Absent,
+            };
+            update_letter_state(&mut states, letter, state);
+        }
+    }
+    states
+}
+
+/// Updates the state of a letter, if the new state is better known.
+/// Characters other than 'a' to 'z' are ignored.
+pub fn update_letter_state(states: &mut [LetterState; 26], letter: char, state: LetterState) {
+    if letter.is_ascii_lowercase() {
+        let index = (letter as u8 - b'a') as usize;
+        states[index] = states[index].max(state);
+    }
+}
+
+/// Returns the Wordle feedback for a guess against an answer.
+///
+/// Each letter of the feedback is one of:
+/// - 'g' (green), the letter is in the correct position
+/// - 'y' (yellow), the letter is in the answer but in another position
+/// - 'b' (black), the letter is not in the answer
+///
+/// Repeated letters are scored like Wordle does: greens are matched
+/// first, then yellows from left to right, so a letter is never
+/// marked more times than it appears in the answer.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::get_feedback;
+///
+/// assert_eq!(get_feedback("crane", "react"), "yygby");
+/// assert_eq!(get_feedback("sheet", "threw"), "bgbgy");
+/// ```
+pub fn get_feedback(guess: &str, answer: &str) -> String {
+    let mut feedback = vec![b'b'; guess.len()];
+    score(guess.as_bytes(), answer.as_bytes(), &mut feedback);
+    String::from_utf8(feedback).unwrap()
+}
+
+// Scores the guess against the answer, filling feedback with 'g', 'y' or
+// 'b' for each letter of the guess. Used where allocating a String for
+// every word would be too slow.
+pub(crate) fn score(guess: &[u8], answer: &[u8], feedback: &mut [u8]) {
+    // number of each letter of the answer not yet matched to the guess
+    let mut unmatched = [0u8; 256];
+
+    for i in 0..guess.len() {
+        if i < answer.len() && guess[i] == answer[i] {
+            feedback[i] = b'g';
+        } else {
+            feedback[i] = b'b';
+            if i < answer.len() {
+                unmatched[answer[i] as usize] += 1;
+            }
+        }
+    }
+    for &c in answer.iter().skip(guess.len()) {
+        unmatched[c as usize] += 1;
+    }
+    for i in 0..guess.len() {
+        if feedback[i] != b'g' && unmatched[guess[i] as usize] > 0 {
+            feedback[i] = b'y';
+            unmatched[guess[i] as usize] -= 1;
+        }
+    }
+}
+
+/// Returns the number of letters the guess has in common with the
+/// answer, in any position. A repeated letter counts as many times as it
+/// is in both words.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::common_letters;
+///
+/// assert_eq!(common_letters("crane", "react"), 4);
+/// assert_eq!(common_letters("llama", "hello"), 2);
+/// ```
+pub fn common_letters(guess: &str, answer: &str) -> usize {
+    // number of each letter of the answer not yet matched to the guess
+    let mut unmatched = [0u8; 256];
+    for &c in answer.as_bytes() {
+        unmatched[c as usize] += 1;
+    }
+    let mut count = 0;
+    for &c in guess.as_bytes() {
+        if unmatched[c as usize] > 0 {
+            unmatched[c as usize] -= 1;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Returns the number of black pegs (symbols of the guess in the same
+/// position in the answer) and white pegs (other symbols of the guess
+/// that are in the answer) for a guess against an answer, as in
+/// Mastermind.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::pegs;
+///
+/// assert_eq!(pegs("1234", "1325"), (1, 2));
+/// assert_eq!(pegs("1122", "2211"), (0, 4));
+/// ```
+pub fn pegs(guess: &str, answer: &str) -> (usize, usize) {
+    let black = guess
+        .bytes()
+        .zip(answer.bytes())
+        .filter(|(g, a)| g == a)
+        .count();
+    (black, common_letters(guess, answer) - black)
+}
+
+// Reads pegs feedback, e.g. "1b2w", as the number of black and white
+// pegs.
+fn parse_pegs(feedback: &str) -> Option<(usize, usize)> {
+    let (black, white) = feedback.strip_suffix('w')?.split_once('b')?;
+    Some((black.parse().ok()?, white.parse().ok()?))
+}
+
+/// Returns every code of the given length made of the symbols, in order,
+/// to use instead of a list of words for Mastermind style games. Without
+/// repeats, a symbol is used at most once in each code (as in Bulls and
+/// Cows). A symbol given more than once is only used as one symbol.
+///
+/// Returns an error if the length is 0, or if there would be more than
+/// [`MAX_CODES`] codes.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::generate_codes;
+///
+/// assert_eq!(generate_codes("12", 2, true).unwrap(), vec!["11", "12", "21", "22"]);
+/// assert_eq!(generate_codes("123", 2, false).unwrap().len(), 6);
+/// assert!(generate_codes("0123456789", 10, true).is_err());
+/// ```
+pub fn generate_codes(symbols: &str, length: usize, repeats: bool) -> Result<Vec<String>, String> {
+    if length == 0 {
+        return Err(String::from("the length of the codes has to be at least 1"));
+    }
+    let mut unique: Vec<char> = Vec::new();
+    for symbol in symbols.chars() {
+        if !unique.contains(&symbol) {
+            unique.push(symbol);
+        }
+    }
+
+    // number of codes: symbols^length, or one symbol less for each
+    // position without repeats
+    let mut count: usize = 1;
+    for i in 0..length {
+        let choices = if repeats { unique.len() } else { unique.len().saturating_sub(i) };
+        count = count.saturating_mul(choices);
+    }
+    if count > MAX_CODES {
+        return Err(format!(
+            "{} symbols of length {} give more than {} codes",
+            unique.len(),
+            length,
+            MAX_CODES
+        ));
+    }
+
+    let mut codes = vec![String::new()];
+    for _ in 0..length {
+        let mut longer = Vec::new();
+        for code in &codes {
+            for &symbol in &unique {
+                if repeats || !code.contains(symbol) {
+                    longer.push(format!("{}{}", code, symbol));
+                }
+            }
+        }
+        codes = longer;
+    }
+    Ok(codes)
+}
+
+/// Returns true if the word uses exactly the letters given, in any order.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::is_anagram;
+///
+/// assert!(is_anagram("react", "trace"));
+/// assert!(!is_anagram("react", "treat"));
+/// ```
+pub fn is_anagram(word: &str, letters: &str) -> bool {
+    word.len() == letters.len() && common_letters(word, letters) == word.len()
+}
+
+/// Returns true if the word only uses the letters given. With repeats,
+/// a letter can be used any number of times, otherwise at most as many
+/// times as it is given.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::uses_only_letters;
+///
+/// assert!(uses_only_letters("added", "ade", true));
+/// assert!(!uses_only_letters("added", "ade", false));
+/// assert!(uses_only_letters("trace", "abcerst", false));
+/// ```
+pub fn uses_only_letters(word: &str, letters: &str, repeats: bool) -> bool {
+    if repeats {
+        word.chars().all(|c| letters.contains(c))
+    } else {
+        common_letters(word, letters) == word.len()
+    }
+}
+
+// Returns the feedback as a number, treating each letter as a base 3
+// digit, to use as an index when grouping words by their feedback.
+pub(crate) fn feedback_index(feedback: &[u8]) -> usize {
+    feedback.iter().fold(0, |index, c| {
+        index * 3 + match c {
+            b'g' => 2,
+            b'y' => 1,
+            _ => 0,
+        }
+    })
+}
+
+// longest words whose feedbacks are grouped by feedback_index(), which
+// needs 3^length groups
+const MAX_INDEXED_LENGTH: usize = 12;
+
+// Returns the number of colors feedbacks a word of the length can get,
+// the number of groups to index with feedback_index(), or None if the
+// word is too long to group its feedbacks that way.
+pub(crate) fn feedback_count(length: usize) -> Option<usize> {
+    if length <= MAX_INDEXED_LENGTH {
+        Some(3usize.pow(length as u32))
+    } else {
+        None
+    }
+}
+
+/// Splits the lines of a word list into the words and their frequencies.
+///
+/// Each line holds a word, optionally followed by how often it is used
+/// (for example `crane 1234`). Blank lines are skipped, and words without
+/// a frequency are left out of the frequencies.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::read_word_list;
+///
+/// let lines = vec![String::from("crane 12.5"), String::from("react")];
+/// let (words, frequencies) = read_word_list(lines);
+/// assert_eq!(words, vec![String::from("crane"), String::from("react")]);
+/// assert_eq!(frequencies.get("crane"), Some(&12.5));
+/// assert_eq!(frequencies.get("react"), None);
+/// ```
+pub fn read_word_list(lines: Vec<String>) -> (Vec<String>, HashMap<String, f64>) {
+    let mut words = Vec::new();
+    let mut frequencies = HashMap::new();
+    for line in lines {
+        let mut columns = line.split_whitespace();
+        let word = match columns.next() {
+            Some(word) => word.to_string(),
+            None => continue,
+        };
+        if let Some(frequency) = columns.next().and_then(|f| f.parse::<f64>().ok()) {
+            frequencies.insert(word.clone(), frequency);
+        }
+        words.push(word);
+    }
+    (words, frequencies)
+}
+
+// Returns the expected information, in bits, of feedback that splits total
+// words into groups of the sizes given.
+pub(crate) fn information(groups: &[usize], total: f64) -> f64 {
+    groups
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total;
+            p * (1.0 / p).log2()
+        })
+        .sum()
+}
+
+/// Reads past answers from the lines of a file.
+///
+/// Each line holds a word and, optionally, the date it was the answer,
+/// separated by spaces or a comma, in either order (for example
+/// `2022-06-19 crane`). Blank lines and lines starting with '#' are
+/// skipped.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::read_past_answers;
+///
+/// let lines = vec![String::from("2022-06-19 crane"), String::from("react")];
+/// let answers = read_past_answers(lines);
+/// assert_eq!(answers[0].word, "crane");
+/// assert_eq!(answers[0].date.as_deref(), Some("2022-06-19"));
+/// assert_eq!(answers[1].date, None);
+/// ```
+pub fn read_past_answers(lines: Vec<String>) -> Vec<PastAnswer> {
+    let mut answers = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut word = None;
+        let mut date = None;
+        for column in line.split(|c: char| c.is_whitespace() || c == ',') {
+            if column.is_empty() {
+                continue;
+            }
+            if word.is_none() && column.chars().all(|c| c.is_ascii_alphabetic()) {
+                word = Some(column.to_ascii_lowercase());
+            } else if date.is_none() {
+                date = Some(column.to_string());
+            }
+        }
+        if let Some(word) = word {
+            answers.push(PastAnswer { word, date });
+        }
+    }
+    answers
+}
+
+/// Returns true if the feedback only contains 'g', 'y' and 'b'
+/// letters and has the same length as the guess.
+pub fn is_valid_feedback(guess: &str, feedback: &str) -> bool {
+    guess.len() == feedback.len() && feedback.chars().all(|c| "gyb".contains(c))
 }
 
 impl WordleWords {
@@ -14,15 +650,247 @@ impl WordleWords {
         WordleWords {
             // current_list is a clone of the initial list at the start
             current_list: initial_list.clone(),
+            base_list: initial_list.clone(),
             original_list: initial_list,
+            guesses: Vec::new(),
+            banned: HashSet::new(),
+            hard_mode: false,
+            frequencies: HashMap::new(),
+            past_answers: HashMap::new(),
+            downweight_past: false,
+            lies: 0,
+            mode: FeedbackMode::Colors,
         }
     }
 
     /// Resets the list of possible words.
     ///
-    /// This clears out all the filters that have been applied.
+    /// This clears out all the filters and guesses that have been applied.
     pub fn reset_list(&mut self) {
+        self.base_list = self.original_list.clone();
         self.current_list = self.original_list.clone();
+        self.guesses.clear();
+        if !self.banned.is_empty() {
+            let banned = std::mem::take(&mut self.banned);
+            self.retain(|word| !banned.contains(word));
+            self.banned = banned;
+        }
+        self.apply_past_answers();
+    }
+
+    /// Removes all the guesses that have been applied, keeping the
+    /// other filters.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("react"),
+    ///     String::from("trace"),
+    ///     String::from("crane"),
+    /// ];
+    /// let mut list = WordleWords::new(v);
+    /// list.remove_letters("n");
+    /// list.apply_guess("crane", "yygby").unwrap();
+    /// assert_eq!(list.get_word_list().len(), 1);
+    /// list.clear_guesses();
+    /// assert_eq!(list.get_word_list().len(), 2);
+    /// ```
+    pub fn clear_guesses(&mut self) {
+        self.current_list = self.base_list.clone();
+        self.guesses.clear();
+    }
+
+    // keep the words, both possible and before any guesses, that keep
+    // returns true for
+    fn retain<F: Fn(&String) -> bool>(&mut self, keep: F) {
+        self.current_list.retain(&keep);
+        self.base_list.retain(&keep);
+    }
+
+    /// Sets the words that were the answer before. They are removed from
+    /// the possible words, or, if downweight is true, kept as less likely
+    /// answers, after the other possible words.
+    pub fn set_past_answers(&mut self, answers: Vec<PastAnswer>, downweight: bool) {
+        self.past_answers = answers
+            .into_iter()
+            .map(|answer| (answer.word.clone(), answer))
+            .collect();
+        self.downweight_past = downweight;
+        self.apply_past_answers();
+    }
+
+    /// Sets whether past answers are kept as less likely answers, rather
+    /// than removed. The possible words change when the list is next
+    /// reset.
+    pub fn set_downweight_past(&mut self, downweight: bool) {
+        self.downweight_past = downweight;
+    }
+
+    /// Returns true if past answers are kept as less likely answers.
+    pub fn is_downweight_past(&self) -> bool {
+        self.downweight_past
+    }
+
+    /// Returns true if any past answers have been set.
+    pub fn has_past_answers(&self) -> bool {
+        !self.past_answers.is_empty()
+    }
+
+    /// Returns the word as a past answer, if it was the answer before.
+    pub fn past_answer(&self, word: &str) -> Option<&PastAnswer> {
+        self.past_answers.get(word)
+    }
+
+    // remove the past answers from the possible words or, if they are
+    // only downweighted, move them after the other possible words
+    fn apply_past_answers(&mut self) {
+        if self.past_answers.is_empty() {
+            return;
+        }
+        let past_answers = std::mem::take(&mut self.past_answers);
+        if self.downweight_past {
+            let last = |word: &String| past_answers.contains_key(word);
+            self.current_list.sort_by_key(last);
+            self.base_list.sort_by_key(last);
+        } else {
+            self.retain(|word| !past_answers.contains_key(word));
+        }
+        self.past_answers = past_answers;
+    }
+
+    // weight of a possible word when working out the information of a
+    // guess: downweighted past answers count for less
+    fn past_weight(&self, word: &str) -> f64 {
+        if self.downweight_past && self.past_answers.contains_key(word) {
+            PAST_ANSWER_WEIGHT
+        } else {
+            1.0
+        }
+    }
+
+    // Returns the weight of each possible word, or None if every possible
+    // word counts as much as any other.
+    fn past_weights(&self) -> Option<Vec<f64>> {
+        let weights: Vec<f64> = self.current_list.iter().map(|w| self.past_weight(w)).collect();
+        if weights.iter().all(|&weight| weight == 1.0) {
+            None
+        } else {
+            Some(weights)
+        }
+    }
+
+    /// Returns the original list of words, before any filters were
+    /// applied.
+    pub fn get_original_list(&self) -> &Vec<String> {
+        &self.original_list
+    }
+
+    /// Returns the guesses, and their feedback, that have been applied.
+    pub fn get_guesses(&self) -> &Vec<(String, String)> {
+        &self.guesses
+    }
+
+    /// Sets whether guesses have to follow the hard mode rules.
+    pub fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+
+    /// Sets the number of tiles of each feedback that can be wrong, as
+    /// in Fibble where one tile of each row lies. A word is then kept if
+    /// it gives each guess's feedback with at most that many tiles
+    /// different, and the possible words are ranked by how many
+    /// feedbacks they give exactly. Hard mode rules are not checked, as
+    /// the feedback cannot be trusted.
+    pub fn set_lies(&mut self, lies: usize) {
+        self.lies = lies;
+    }
+
+    /// Returns the number of tiles of each feedback that can be wrong.
+    pub fn get_lies(&self) -> usize {
+        self.lies
+    }
+
+    /// Returns the number of guesses the word gives exactly the feedback
+    /// of.
+    pub fn matched_rows(&self, word: &str) -> usize {
+        self.guesses
+            .iter()
+            .filter(|(guess, feedback)| self.mode.errors(guess, feedback, word) == 0)
+            .count()
+    }
+
+    /// Sets how the game scores a guess, e.g. with the number of letters
+    /// in common for Jotto. This is meant to be done before any guesses
+    /// are applied.
+    pub fn set_feedback_mode(&mut self, mode: FeedbackMode) {
+        self.mode = mode;
+    }
+
+    /// Returns how the game scores a guess.
+    pub fn get_feedback_mode(&self) -> FeedbackMode {
+        self.mode
+    }
+
+    /// Returns true if guesses have to follow the hard mode rules.
+    pub fn is_hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Sets how often each word is used. Words that are not in
+    /// frequencies are taken to be never used, unless frequencies is
+    /// empty, when every word is as likely as any other.
+    pub fn set_frequencies(&mut self, frequencies: HashMap<String, f64>) {
+        self.frequencies = frequencies;
+    }
+
+    /// Returns how often the word is used, if it is known.
+    pub fn frequency(&self, word: &str) -> Option<f64> {
+        self.frequencies.get(word).copied()
+    }
+
+    // weight of a word when working out how likely it is to be the answer
+    fn weight(&self, word: &str) -> f64 {
+        let weight = if self.frequencies.is_empty() {
+            1.0
+        } else {
+            self.frequency(word).unwrap_or(0.0)
+        };
+        weight * self.past_weight(word)
+    }
+
+    /// Returns the probability that the word is the answer, from the
+    /// frequencies of the possible words. Without frequencies, every
+    /// possible word is as likely as any other. Downweighted past answers
+    /// are much less likely.
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashMap;
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![String::from("crane"), String::from("react")];
+    /// let mut list = WordleWords::new(v);
+    /// assert_eq!(list.prior("crane"), 0.5);
+    /// let frequencies = HashMap::from([
+    ///     (String::from("crane"), 3.0),
+    ///     (String::from("react"), 1.0),
+    /// ]);
+    /// list.set_frequencies(frequencies);
+    /// assert_eq!(list.prior("crane"), 0.75);
+    /// assert_eq!(list.prior("boxes"), 0.0);
+    /// ```
+    pub fn prior(&self, word: &str) -> f64 {
+        if !self.current_list.iter().any(|w| w == word) {
+            return 0.0;
+        }
+        let total: f64 = self.current_list.iter().map(|w| self.weight(w)).sum();
+        if total > 0.0 {
+            self.weight(word) / total
+        } else {
+            0.0
+        }
     }
 
     /// Returns the current list of possible words after filters have
@@ -35,7 +903,7 @@ impl WordleWords {
     ///
     /// # Example
     /// ```
-    /// use WordleWords;
+    /// use wordlesolver::wordle::WordleWords;
     ///
     /// let v = vec![
     ///     String::from("aaaaa"),
@@ -50,34 +918,89 @@ impl WordleWords {
     /// assert_eq!("ccccc", result[1]);
     /// ```
     pub fn remove_letters(&mut self, letters : &str) {
-        let mut new_list: Vec<String> = Vec::new();
-        // add the word to the new list if there is no match to any
-        // letter in the remove list
-        for word in &self.current_list {
-            let mut matched = false;
+        // keep the word if there is no match to any letter in the
+        // remove list
+        self.retain(|word| {
             for letter in letters.chars() {
                 if word.contains(letter) {
-                    matched = true;
-                    break;
+                    return false;
                 }
             }
-            if !matched {
-                new_list.push(word.clone());
+            true
+        });
+    }
+
+    /// Removes the words from the list of possible words.
+    pub fn remove_words(&mut self, words: &[String]) {
+        self.retain(|word| !words.contains(word));
+    }
+
+    /// Removes the words from the possible words for good, even when
+    /// the list is reset, and from the suggested guesses, e.g. for words
+    /// the game rejects. They can still be guessed.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![String::from("crane"), String::from("react")];
+    /// let mut list = WordleWords::new(v);
+    /// list.ban_words(&[String::from("react")]);
+    /// list.reset_list();
+    /// assert_eq!(list.get_word_list(), &vec![String::from("crane")]);
+    /// assert_eq!(list.get_original_list().len(), 2);
+    /// assert_eq!(list.suggest(2).len(), 1);
+    /// ```
+    pub fn ban_words(&mut self, words: &[String]) {
+        self.banned.extend(words.iter().cloned());
+        self.retain(|word| !words.contains(word));
+    }
+
+    /// Returns true if the word was banned (see [`WordleWords::ban_words`]).
+    pub fn is_banned(&self, word: &str) -> bool {
+        self.banned.contains(word)
+    }
+
+    /// Adds the words to the list of words, and to the possible words,
+    /// if they are not already in it. This is meant to be done before
+    /// any filters or guesses are applied.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![String::from("crane"), String::from("react")];
+    /// let mut list = WordleWords::new(v);
+    /// list.ban_words(&[String::from("react")]);
+    /// list.pin_words(&[String::from("crane"), String::from("trace")]);
+    /// assert_eq!(list.get_word_list(), &vec![String::from("crane"), String::from("trace")]);
+    /// list.reset_list();
+    /// assert_eq!(list.get_word_list().len(), 2);
+    /// ```
+    pub fn pin_words(&mut self, words: &[String]) {
+        for word in words {
+            self.banned.remove(word);
+            if !self.original_list.contains(word) {
+                self.original_list.push(word.clone());
+            }
+            if !self.current_list.contains(word) {
+                self.current_list.push(word.clone());
+            }
+            if !self.base_list.contains(word) {
+                self.base_list.push(word.clone());
             }
         }
-        // replace the current list with the new one
-        self.current_list = new_list;
     }
 
     /// Include words that contain the letters in the correct location(s)
     ///
-    /// Letters should be five chars.
-    ///
-    /// Unknown letters should be replaced by a '.'
+    /// Letters should be as long as the words, with unknown letters
+    /// replaced by a '.'. Returns an error, and keeps every word, if they
+    /// are as long as none of the words.
     ///
     /// # Example
     /// ```
-    /// use WordleWords;
+    /// use wordlesolver::wordle::WordleWords;
     ///
     /// let v = vec![
     ///     String::from("aaaaa"),
@@ -85,50 +1008,142 @@ impl WordleWords {
     ///     String::from("ccccc"),
     /// ];
     /// let mut list = WordleWords::new(v);
-    /// list.correct_letters("a....");
+    /// list.correct_letters("a....").unwrap();
     /// let result = list.get_word_list();
     /// assert_eq!(result.len(), 1);
     /// assert_eq!("aaaaa", result[0]);
     /// ```
-    pub fn correct_letters(&mut self, letters: &str) {
-        let i = letters.len();
-        if i != 5 {
-            println!("length of correct letters is not 5");
-            return;
+    pub fn correct_letters(&mut self, letters: &str) -> Result<(), String> {
+        let letters: Vec<char> = letters.chars().collect();
+        if !self.check_positions(&letters, "correct")? {
+            return Ok(());
         }
 
-        let mut new_list: Vec<String> = Vec::new();
-        // add the word to the new list if all the letters in the
-        // correct list (excluding '.') match the word
-        for word in &self.current_list {
-            let mut matched = true;
-            for j in 0 .. 5 {
-                match &letters[j .. (j+1)] {
-                    "." => (),
-                    x => {
-                        if x != &word[j .. (j+1)] {
-                            matched = false;
-                        }
-                    }
-                }
-            }
-            if matched {
-                new_list.push(word.clone());
+        // keep the word if all the letters in the correct list
+        // (excluding '.') match the word
+        self.retain(|word| {
+            word.chars().count() == letters.len()
+                && word
+                    .chars()
+                    .zip(&letters)
+                    .all(|(c, &letter)| letter == '.' || letter == c)
+        });
+        Ok(())
+    }
+
+    // Returns false if every letter is '.', so there is nothing to
+    // filter, and an error if the letters are as long as none of the
+    // words.
+    fn check_positions(&self, letters: &[char], name: &str) -> Result<bool, String> {
+        if letters.iter().all(|&letter| letter == '.') {
+            return Ok(false);
+        }
+        if !self.original_list.iter().any(|word| word.chars().count() == letters.len()) {
+            return Err(format!(
+                "length of {} letters is not the length of the words",
+                name
+            ));
+        }
+        Ok(true)
+    }
+
+    /// Keep words that are anagrams of the letters: they use every letter
+    /// given, as many times as it is given, in any order. The letters can
+    /// be upper or lower case.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("react"),
+    ///     String::from("trace"),
+    ///     String::from("crane"),
+    /// ];
+    /// let mut list = WordleWords::new(v);
+    /// list.correct_letters("t....").unwrap();
+    /// list.anagram_letters("caret");
+    /// assert_eq!(list.get_word_list(), &vec![String::from("trace")]);
+    /// ```
+    pub fn anagram_letters(&mut self, letters: &str) {
+        let letters = letters.to_lowercase();
+        self.retain(|word| is_anagram(word, &letters));
+    }
+
+    /// Keep words that only use the letters. With repeats, a letter can be
+    /// used any number of times, otherwise at most as many times as it is
+    /// given. The letters can be upper or lower case.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("react"),
+    ///     String::from("eerie"),
+    ///     String::from("crane"),
+    /// ];
+    /// let mut list = WordleWords::new(v);
+    /// list.only_letters("acerti", true);
+    /// assert_eq!(list.get_word_list().len(), 2);
+    /// list.only_letters("acerti", false);
+    /// assert_eq!(list.get_word_list(), &vec![String::from("react")]);
+    /// ```
+    pub fn only_letters(&mut self, letters: &str, repeats: bool) {
+        let letters = letters.to_lowercase();
+        self.retain(|word| uses_only_letters(word, &letters, repeats));
+    }
+
+    /// Keep words that match a crossword style query (see [`Query`]).
+    ///
+    /// Returns an error, and keeps every word, if the query cannot be
+    /// read, or has a pattern as long as none of the words.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("react"),
+    ///     String::from("trace"),
+    ///     String::from("crane"),
+    /// ];
+    /// let mut list = WordleWords::new(v);
+    /// list.apply_query("?ra?e -n").unwrap();
+    /// assert_eq!(list.get_word_list(), &vec![String::from("trace")]);
+    /// assert!(list.apply_query("e").is_err());
+    /// ```
+    pub fn apply_query(&mut self, query: &str) -> Result<(), String> {
+        let query = self.check_query(query)?;
+        self.retain(|word| query.matches(word));
+        Ok(())
+    }
+
+    /// Reads a query (see [`Query`]) to use with the list of words,
+    /// returning an error if it cannot be read, or has a pattern as long
+    /// as none of the words.
+    pub fn check_query(&self, query: &str) -> Result<Query, String> {
+        let query = Query::parse(query)?;
+        for pattern in &query.patterns {
+            if !self.original_list.iter().any(|word| word.chars().count() == pattern.len()) {
+                return Err(format!(
+                    "a pattern of length {} cannot match any word (use +letters for letters in the word)",
+                    pattern.len()
+                ));
             }
         }
-        // replace the current list with the new one
-        self.current_list = new_list;
+        Ok(query)
     }
 
     /// Include words that contain the letters but in the incorrect
     /// location(s)
     ///
-    /// Letters should be five chars.
-    ///
-    /// Unknown letters should be replaced by a '.'
+    /// Letters should be as long as the words, with unknown letters
+    /// replaced by a '.'. Returns an error, and keeps every word, if they
+    /// are as long as none of the words.
     ///
     /// ```
-    /// use WordleWords;
+    /// use wordlesolver::wordle::WordleWords;
     ///
     /// let v = vec![
     ///     String::from("aaaaa"),
@@ -136,40 +1151,358 @@ impl WordleWords {
     ///     String::from("ccccc"),
     /// ];
     /// let mut list = WordleWords::new(v);
-    /// list.incorrect_letters(".a...");
+    /// list.incorrect_letters(".a...").unwrap();
     /// let result = list.get_word_list();
     /// assert_eq!(result.len(), 1);
     /// assert_eq!("bbabb", result[0]);
     /// ```
-    pub fn incorrect_letters(&mut self, letters: &str) {
-        let i = letters.len();
-        // println!("{} {}", letters, i);
-        if i != 5 {
-            println!("length of incorrect letters is not 5");
-            return;
+    pub fn incorrect_letters(&mut self, letters: &str) -> Result<(), String> {
+        let letters: Vec<char> = letters.chars().collect();
+        if !self.check_positions(&letters, "incorrect")? {
+            return Ok(());
         }
-        let mut new_list: Vec<String> = Vec::new();
-        // add the word to the new list if there is no match to the
-        // letters in the incorrect location list (excluding '.')
-        for word in &self.current_list {
-            let mut expected_matches = 5; // decremented when a '.' is encountered
-            let mut match_found = 0; // incremented when a match is found but not at the expected location
-            for j in 0 .. 5 {
-                match &letters[j .. (j+1)] {
-                    "." => expected_matches -= 1,
-                    x => {
-                        if word.contains(x) && x != &word[j .. (j+1)] {
-                            match_found += 1;
+
+        // keep the word if there is no match to the letters in the
+        // incorrect location list (excluding '.'), and they are all
+        // somewhere else in the word
+        self.retain(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            chars.len() == letters.len()
+                && letters
+                    .iter()
+                    .zip(&chars)
+                    .all(|(&letter, &c)| letter == '.' || (letter != c && chars.contains(&letter)))
+        });
+        Ok(())
+    }
+
+    /// Returns true if the guess follows the hard mode rules, given
+    /// the guesses that have been applied so far.
+    ///
+    /// Green letters have to be kept in the same position, and yellow
+    /// letters have to be used somewhere in the guess.
+    pub fn is_hard_mode_guess(&self, guess: &str) -> bool {
+        let guess = guess.as_bytes();
+        for (previous, feedback) in &self.guesses {
+            let previous = previous.as_bytes();
+            let feedback = feedback.as_bytes();
+            for j in 0..feedback.len() {
+                match feedback[j] {
+                    b'g' if guess.get(j) != Some(&previous[j]) => return false,
+                    b'y' => {
+                        // the guess needs at least as many of this letter as
+                        // were revealed by the previous guess
+                        let letter = previous[j];
+                        let revealed = (0..feedback.len())
+                            .filter(|&k| previous[k] == letter && feedback[k] != b'b')
+                            .count();
+                        let used = guess.iter().filter(|&&c| c == letter).count();
+                        if used < revealed {
+                            return false;
                         }
                     }
+                    _ => (),
                 }
             }
-            if expected_matches == match_found {
-                new_list.push(word.clone());
+        }
+        true
+    }
+
+    /// Keep words that would give the same feedback for the guess
+    ///
+    /// Feedback uses 'g', 'y' and 'b' for each letter of the guess (see
+    /// [`get_feedback`]), or is the number of letters in common with the
+    /// answer if the feedback mode is [`FeedbackMode::Count`].
+    ///
+    /// Returns an error, and keeps every word, if the feedback is not
+    /// valid for the guess, or the guess does not follow the hard mode
+    /// rules.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("react"),
+    ///     String::from("trace"),
+    ///     String::from("crane"),
+    /// ];
+    /// let mut list = WordleWords::new(v);
+    /// list.apply_guess("crane", "yygby").unwrap();
+    /// let result = list.get_word_list();
+    /// assert_eq!(result.len(), 1);
+    /// assert_eq!("react", result[0]);
+    /// assert!(list.apply_guess("crane", "yyg").is_err());
+    /// ```
+    pub fn apply_guess(&mut self, guess: &str, feedback: &str) -> Result<(), String> {
+        if !self.mode.is_valid(guess, feedback) {
+            return Err(format!(
+                "feedback {} is not valid for guess {}",
+                feedback, guess
+            ));
+        }
+        if self.hard_mode && self.lies == 0 && !self.is_hard_mode_guess(guess) {
+            return Err(format!(
+                "guess {} does not follow the hard mode rules",
+                guess
+            ));
+        }
+
+        let lies = self.lies;
+        let mode = self.mode;
+        self.current_list
+            .retain(|word| mode.errors(guess, feedback, word) <= lies);
+        self.guesses.push((guess.to_string(), feedback.to_string()));
+
+        if lies > 0 {
+            // the words matching the most feedbacks exactly first
+            let guesses = &self.guesses;
+            self.current_list.sort_by_cached_key(|word| {
+                let matched = guesses
+                    .iter()
+                    .filter(|(guess, feedback)| mode.errors(guess, feedback, word) == 0)
+                    .count();
+                std::cmp::Reverse(matched)
+            });
+        }
+        Ok(())
+    }
+
+    /// Removes the last guess that was applied, and returns it.
+    ///
+    /// The list is rebuilt by applying the remaining guesses to the
+    /// words left by the other filters, which are kept.
+    pub fn undo_guess(&mut self) -> Option<(String, String)> {
+        let last = self.guesses.pop()?;
+        let guesses = std::mem::take(&mut self.guesses);
+        self.clear_guesses();
+        for (guess, feedback) in guesses {
+            // the guesses were accepted before, so they still are
+            let _ = self.apply_guess(&guess, &feedback);
+        }
+        Some(last)
+    }
+
+    /// Returns a sentence explaining why the word is, or is not, one
+    /// of the possible words.
+    pub fn explain(&self, word: &str) -> String {
+        if !self.original_list.iter().any(|w| w == word) {
+            return format!("{} is not in the list of words", word);
+        }
+        if self.current_list.iter().any(|w| w == word) {
+            return format!("{} is a possible word", word);
+        }
+        for (guess, feedback) in &self.guesses {
+            let expected = self.mode.feedback(guess, word);
+            if self.mode.errors(guess, feedback, word) > self.lies {
+                return format!(
+                    "{} is ruled out by {}: the feedback would be {}, not {}",
+                    word, guess, expected, feedback
+                );
             }
         }
-        // replace the current list with the new one
-        self.current_list = new_list;
+        if self.banned.contains(word) {
+            return format!("{} is banned", word);
+        }
+        if let Some(answer) = self.past_answer(word) {
+            if !self.downweight_past {
+                return match &answer.date {
+                    Some(date) => format!("{} was the answer on {}", word, date),
+                    None => format!("{} was a past answer", word),
+                };
+            }
+        }
+        format!("{} is ruled out by the letter filters", word)
+    }
+
+    // Returns the number of feedbacks the guess can get, the number of
+    // groups of feedback_groups(), or None if the guess is too long to
+    // index its feedbacks.
+    fn group_count(&self, guess: &str) -> Option<usize> {
+        match self.mode {
+            FeedbackMode::Colors => feedback_count(guess.len()),
+            FeedbackMode::Count => Some(guess.len() + 1),
+            FeedbackMode::Pegs => Some((guess.len() + 1) * (guess.len() + 1)),
+        }
+    }
+
+    // Returns the group of the feedback to the guess against the word:
+    // indexed by feedback_index(), by the count of common letters, or by
+    // the number of black and white pegs. feedback is as long as the
+    // guess, to score the colors in.
+    fn group_index(&self, guess: &str, word: &str, feedback: &mut [u8]) -> usize {
+        match self.mode {
+            FeedbackMode::Colors => {
+                score(guess.as_bytes(), word.as_bytes(), feedback);
+                feedback_index(feedback)
+            }
+            FeedbackMode::Count => common_letters(guess, word),
+            FeedbackMode::Pegs => {
+                let (black, white) = pegs(guess, word);
+                black * (guess.len() + 1) + white
+            }
+        }
+    }
+
+    // Returns the number of possible words for each feedback to the guess,
+    // indexed by group_index(), or in no order if the guess is too long
+    // to index its feedbacks.
+    fn feedback_groups(&self, guess: &str) -> Vec<usize> {
+        let count = match self.group_count(guess) {
+            Some(count) => count,
+            None => {
+                let mut groups: HashMap<String, usize> = HashMap::new();
+                for word in &self.current_list {
+                    *groups.entry(self.mode.feedback(guess, word)).or_insert(0) += 1;
+                }
+                return groups.into_values().collect();
+            }
+        };
+        let mut groups = vec![0; count];
+        let mut feedback = vec![b'b'; guess.len()];
+        for word in &self.current_list {
+            groups[self.group_index(guess, word, &mut feedback)] += 1;
+        }
+        groups
+    }
+
+    // Returns the expected information, in bits, from the feedback to the
+    // guess, with each possible word counting for its weight.
+    fn weighted_information(&self, guess: &str, weights: &[f64]) -> f64 {
+        let groups: Vec<f64> = match self.group_count(guess) {
+            Some(count) => {
+                let mut groups = vec![0.0; count];
+                let mut feedback = vec![b'b'; guess.len()];
+                for (word, weight) in self.current_list.iter().zip(weights) {
+                    groups[self.group_index(guess, word, &mut feedback)] += weight;
+                }
+                groups
+            }
+            None => {
+                let mut groups: HashMap<String, f64> = HashMap::new();
+                for (word, weight) in self.current_list.iter().zip(weights) {
+                    *groups.entry(self.mode.feedback(guess, word)).or_insert(0.0) += weight;
+                }
+                groups.into_values().collect()
+            }
+        };
+        let total: f64 = weights.iter().sum();
+        groups
+            .iter()
+            .filter(|&&w| w > 0.0)
+            .map(|&w| {
+                let p = w / total;
+                p * (1.0 / p).log2()
+            })
+            .sum()
+    }
+
+    /// Returns each feedback the guess can get, and the number of possible
+    /// words that would give it, most words first.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("bakes"),
+    ///     String::from("cakes"),
+    ///     String::from("makes"),
+    /// ];
+    /// let list = WordleWords::new(v);
+    /// let result = list.feedback_distribution("bakes");
+    /// assert_eq!(result[0], (String::from("bgggg"), 2));
+    /// assert_eq!(result[1], (String::from("ggggg"), 1));
+    /// ```
+    pub fn feedback_distribution(&self, guess: &str) -> Vec<(String, usize)> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for word in &self.current_list {
+            *counts.entry(self.mode.feedback(guess, word)).or_insert(0) += 1;
+        }
+        let mut distribution: Vec<(String, usize)> = counts.into_iter().collect();
+        distribution.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        distribution
+    }
+
+    /// Returns the expected information, in bits, from the feedback to
+    /// the guess, if every possible word is as likely as any other, apart
+    /// from downweighted past answers.
+    pub fn information(&self, guess: &str) -> f64 {
+        match self.past_weights() {
+            Some(weights) => self.weighted_information(guess, &weights),
+            None => {
+                let total = self.current_list.len() as f64;
+                information(&self.feedback_groups(guess), total)
+            }
+        }
+    }
+
+    /// Returns the number of possible words that are expected to be left
+    /// after the guess, if every possible word is as likely as any other.
+    pub fn expected_remaining(&self, guess: &str) -> f64 {
+        if self.current_list.is_empty() {
+            return 0.0;
+        }
+        let squares: usize = self.feedback_groups(guess).iter().map(|n| n * n).sum();
+        squares as f64 / self.current_list.len() as f64
+    }
+
+    /// Returns up to count suggestions for the next guess, best first.
+    ///
+    /// Every word in the original list is scored by the expected
+    /// information (in bits) of its feedback against the possible
+    /// words. Ties are broken in favour of possible words. Banned words
+    /// are not suggested, and in hard mode, only guesses that follow the
+    /// hard mode rules are. Downweighted past answers count for less in
+    /// the expected information, as they are less likely answers.
+    ///
+    /// # Example
+    /// ```
+    /// use wordlesolver::wordle::WordleWords;
+    ///
+    /// let v = vec![
+    ///     String::from("bakes"),
+    ///     String::from("cakes"),
+    ///     String::from("makes"),
+    ///     String::from("bcmxx"),
+    /// ];
+    /// let mut list = WordleWords::new(v);
+    /// list.correct_letters(".akes").unwrap();
+    /// let result = list.suggest(1);
+    /// assert_eq!("bcmxx", result[0].word);
+    /// ```
+    pub fn suggest(&self, count: usize) -> Vec<Suggestion> {
+        let total = self.current_list.len() as f64;
+        let mut suggestions: Vec<Suggestion> = Vec::new();
+        if self.current_list.is_empty() {
+            return suggestions;
+        }
+        let weights = self.past_weights();
+
+        for guess in &self.original_list {
+            if self.banned.contains(guess) || (self.hard_mode && !self.is_hard_mode_guess(guess)) {
+                continue;
+            }
+            let groups = self.feedback_groups(guess);
+            let information = match &weights {
+                Some(weights) => self.weighted_information(guess, weights),
+                None => information(&groups, total),
+            };
+            suggestions.push(Suggestion {
+                word: guess.clone(),
+                information,
+                worst_case: groups.iter().copied().max().unwrap_or(0),
+                possible: self.current_list.contains(guess),
+            });
+        }
+
+        suggestions.sort_by(|a, b| {
+            b.information
+                .total_cmp(&a.information)
+                .then(b.possible.cmp(&a.possible))
+        });
+        suggestions.truncate(count);
+        suggestions
     }
 }
 
@@ -231,7 +1564,7 @@ mod tests {
             String::from("abcde"),
         ];
         let mut list = WordleWords::new(v);
-        list.correct_letters("a.a.a");
+        list.correct_letters("a.a.a").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 2);
         assert_eq!("aaaaa", result[0]);
@@ -248,7 +1581,7 @@ mod tests {
             String::from("abcde"),
         ];
         let mut list = WordleWords::new(v);
-        list.correct_letters("a....");
+        list.correct_letters("a....").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 3);
         assert_eq!("aaaaa", result[0]);
@@ -266,7 +1599,7 @@ mod tests {
             String::from("bacde"),
         ];
         let mut list = WordleWords::new(v);
-        list.incorrect_letters("a....");
+        list.incorrect_letters("a....").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 1);
         assert_eq!("bacde", result[0]);
@@ -282,7 +1615,7 @@ mod tests {
             String::from("abcde"),
         ];
         let mut list = WordleWords::new(v);
-        list.incorrect_letters("..b..");
+        list.incorrect_letters("..b..").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 1);
         assert_eq!("abcde", result[0]);
@@ -298,7 +1631,7 @@ mod tests {
             String::from("abcde"),
         ];
         let mut list = WordleWords::new(v);
-        list.incorrect_letters(".d.d.");
+        list.incorrect_letters(".d.d.").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 0);
     }
@@ -314,7 +1647,7 @@ mod tests {
         ];
         let mut list = WordleWords::new(v);
         list.remove_letters("a");
-        list.correct_letters("b....");
+        list.correct_letters("b....").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 1);
         assert_eq!("bbbbb", result[0]);
@@ -331,8 +1664,8 @@ mod tests {
         ];
         let mut list = WordleWords::new(v);
         list.remove_letters("a");
-        list.correct_letters("bb...");
-        list.incorrect_letters("....b");
+        list.correct_letters("bb...").unwrap();
+        list.incorrect_letters("....b").unwrap();
         let result = list.get_word_list();
         assert_eq!(result.len(), 1);
         assert_eq!("bbbbc", result[0]);
@@ -358,4 +1691,455 @@ mod tests {
         assert_eq!("bbbbb", result[1]);
         assert_eq!("ccccc", result[2]);
     }
+
+    #[test]
+    fn feedback_test_1() {
+        assert_eq!(get_feedback("crane", "crane"), "ggggg");
+        assert_eq!(get_feedback("crane", "react"), "yygby");
+        assert_eq!(get_feedback("abcde", "fghij"), "bbbbb");
+    }
+
+    #[test]
+    fn feedback_test_2() {
+        // repeated letters are only marked as often as they appear
+        assert_eq!(get_feedback("sheet", "threw"), "bgbgy");
+        assert_eq!(get_feedback("eerie", "there"), "ybybg");
+        assert_eq!(get_feedback("llama", "hello"), "yybbb");
+    }
+
+    #[test]
+    fn apply_guess_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+            String::from("dummy"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.apply_guess("crane", "yygby").unwrap();
+        let result = list.get_word_list();
+        assert_eq!(result.len(), 1);
+        assert_eq!("react", result[0]);
+        assert_eq!(list.get_guesses().len(), 1);
+        list.reset_list();
+        assert_eq!(list.get_word_list().len(), 4);
+        assert_eq!(list.get_guesses().len(), 0);
+    }
+
+    #[test]
+    fn apply_guess_test_2() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+        ];
+        let mut list = WordleWords::new(v);
+        // invalid feedback is rejected
+        assert!(list.apply_guess("crane", "yyyb").is_err());
+        assert!(list.apply_guess("crane", "yyyxb").is_err());
+        assert_eq!(list.get_word_list().len(), 2);
+        assert_eq!(list.get_guesses().len(), 0);
+    }
+
+    #[test]
+    fn hard_mode_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crate"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.set_hard_mode(true);
+        list.apply_guess("crane", "gggbg").unwrap();
+        assert!(list.is_hard_mode_guess("crate"));
+        assert!(!list.is_hard_mode_guess("trace"));
+        // a guess that does not follow the rules is not applied
+        assert!(list.apply_guess("trace", "ygygg").is_err());
+        assert_eq!(list.get_guesses().len(), 1);
+        list.apply_guess("crate", "ggggg").unwrap();
+        assert_eq!(list.get_guesses().len(), 2);
+        assert_eq!(list.get_word_list().len(), 1);
+    }
+
+    #[test]
+    fn hard_mode_test_2() {
+        let v = vec![String::from("react")];
+        let mut list = WordleWords::new(v);
+        list.apply_guess("crane", "yygby").unwrap();
+        assert!(list.is_hard_mode_guess("react"));
+        assert!(list.is_hard_mode_guess("brace"));
+        // the 'c' is missing
+        assert!(!list.is_hard_mode_guess("brave"));
+    }
+
+    #[test]
+    fn letter_states_test_1() {
+        let guesses = vec![
+            (String::from("crane"), String::from("bygbb")),
+            // the second 'e' is absent, but the first one is present
+            (String::from("eerie"), String::from("ybybb")),
+        ];
+        let states = letter_states(&guesses);
+        assert_eq!(states[(b'e' - b'a') as usize], LetterState::Present);
+        assert_eq!(states[(b'i' - b'a') as usize], LetterState::Absent);
+        assert_eq!(states[(b'a' - b'a') as usize], LetterState::Placed);
+        assert_eq!(states[(b'q' - b'a') as usize], LetterState::Unknown);
+    }
+
+    #[test]
+    fn undo_guess_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.apply_guess("crane", "yggbg").unwrap();
+        list.apply_guess("trace", "ggggg").unwrap();
+        assert_eq!(list.get_word_list().len(), 1);
+        let last = list.undo_guess().unwrap();
+        assert_eq!(last, (String::from("trace"), String::from("ggggg")));
+        assert_eq!(list.get_word_list().len(), 1);
+        assert_eq!(list.get_guesses().len(), 1);
+        list.undo_guess();
+        assert_eq!(list.get_word_list().len(), 3);
+        assert!(list.undo_guess().is_none());
+    }
+
+    #[test]
+    fn undo_guess_test_2() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+            String::from("bored"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.remove_letters("o");
+        list.apply_guess("crane", "yygby").unwrap();
+        // filters applied after a guess are kept as well
+        list.apply_query("+t").unwrap();
+        list.undo_guess();
+        assert_eq!(list.get_word_list(), &vec![String::from("react"), String::from("trace")]);
+        list.apply_guess("crane", "yygby").unwrap();
+        list.clear_guesses();
+        assert_eq!(list.get_word_list().len(), 2);
+        list.reset_list();
+        assert_eq!(list.get_word_list().len(), 4);
+    }
+
+    #[test]
+    fn explain_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.apply_guess("crane", "yygby").unwrap();
+        assert_eq!(list.explain("react"), "react is a possible word");
+        assert_eq!(
+            list.explain("trace"),
+            "trace is ruled out by crane: the feedback would be yggbg, not yygby"
+        );
+        assert_eq!(list.explain("xxxxx"), "xxxxx is not in the list of words");
+    }
+
+    #[test]
+    fn suggest_test_1() {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("makes"),
+            String::from("bcmxx"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.correct_letters(".akes").unwrap();
+        let result = list.suggest(2);
+        assert_eq!(result.len(), 2);
+        // "bcmxx" tells the three possible words apart
+        assert_eq!("bcmxx", result[0].word);
+        assert!(!result[0].possible);
+        assert!((result[0].information - 3f64.log2()).abs() < 1e-9);
+        assert_eq!(result[0].worst_case, 1);
+        // a possible word is preferred over an equally good guess
+        assert!(result[1].possible);
+    }
+
+    #[test]
+    fn expected_remaining_test_1() {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("makes"),
+            String::from("bcmxx"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.correct_letters(".akes").unwrap();
+        // splits the three words apart
+        assert_eq!(list.expected_remaining("bcmxx"), 1.0);
+        // two words give the same feedback: (2 * 2 + 1 * 1) / 3
+        assert!((list.expected_remaining("bakes") - 5.0 / 3.0).abs() < 1e-9);
+        list.remove_words(&[String::from("cakes")]);
+        assert_eq!(list.get_word_list().len(), 2);
+    }
+
+    #[test]
+    fn past_answers_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+        ];
+        let answers = read_past_answers(vec![String::from("trace,2022-01-02")]);
+        let mut list = WordleWords::new(v);
+
+        // excluded, even after a reset
+        list.set_past_answers(answers.clone(), false);
+        assert_eq!(list.get_word_list().len(), 2);
+        list.apply_guess("crane", "yygby").unwrap();
+        list.reset_list();
+        assert_eq!(list.get_word_list().len(), 2);
+        assert_eq!(list.explain("trace"), "trace was the answer on 2022-01-02");
+
+        // downweighted: still possible, but less likely, so listed last
+        list.set_past_answers(answers, true);
+        list.reset_list();
+        assert_eq!(list.get_word_list().len(), 3);
+        assert_eq!(list.get_word_list()[2], "trace");
+        assert!(list.prior("trace") < list.prior("react"));
+        let total: f64 = list.get_word_list().iter().map(|w| list.prior(w)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn past_answers_test_2() {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("makes"),
+        ];
+        let answers = read_past_answers(vec![String::from("bakes")]);
+        let mut list = WordleWords::new(v);
+        // each word only tells itself apart from the other two
+        let result = list.suggest(3);
+        assert_eq!(result[0].word, "bakes");
+        assert_eq!(result[0].information, result[2].information);
+
+        // "bakes" is unlikely, so telling it apart tells little, but
+        // telling "cakes" from "makes" tells about a bit
+        list.set_past_answers(answers, true);
+        let result = list.suggest(3);
+        assert_eq!(result[2].word, "bakes");
+        assert!(result[0].information > 0.9);
+        assert!(result[2].information < 0.5);
+        assert_eq!(list.information("bakes"), result[2].information);
+
+        // toggled back to excluding them
+        list.set_downweight_past(false);
+        list.reset_list();
+        assert_eq!(list.get_word_list().len(), 2);
+        assert!(!list.is_downweight_past());
+        assert!(list.has_past_answers());
+    }
+
+    #[test]
+    fn lies_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("boxes"),
+            String::from("bored"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.apply_guess("crane", "bybby").unwrap();
+        assert_eq!(list.get_word_list(), &vec![String::from("bored")]);
+
+        // "bbbby" (boxes) is one tile off, "yygby" (react) two
+        list.set_lies(1);
+        list.reset_list();
+        list.apply_guess("crane", "bybby").unwrap();
+        assert_eq!(
+            list.get_word_list(),
+            &vec![String::from("bored"), String::from("boxes")]
+        );
+        assert_eq!(list.matched_rows("bored"), 1);
+        assert_eq!(list.matched_rows("boxes"), 0);
+        assert_eq!(list.get_lies(), 1);
+    }
+
+    #[test]
+    fn suggest_test_2() {
+        let v = vec![
+            String::from("bakes"),
+            String::from("cakes"),
+            String::from("bcmxx"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.set_hard_mode(true);
+        list.apply_guess("lakes", "bgggg").unwrap();
+        let result = list.suggest(5);
+        // "bcmxx" does not follow the hard mode rules
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|s| s.possible));
+    }
+
+    #[test]
+    fn suggest_test_3() {
+        // too long to index the feedbacks, so they are grouped by string
+        let v = vec![
+            String::from("abcdefghijklmn"),
+            String::from("abcdefghijklmo"),
+            String::from("zzcdefghijklmn"),
+        ];
+        let list = WordleWords::new(v);
+        assert_eq!(feedback_count(14), None);
+        let result = list.suggest(1);
+        assert_eq!(result[0].worst_case, 1);
+        assert!((list.expected_remaining("abcdefghijklmn") - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn count_mode_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+            String::from("bored"),
+            String::from("boxes"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.set_feedback_mode(FeedbackMode::Count);
+        assert!(!FeedbackMode::Count.is_valid("bored", "6"));
+        assert!(!FeedbackMode::Count.is_valid("bored", "bygbb"));
+
+        // "boxes" has three letters in common with "bored"
+        list.apply_guess("bored", "2").unwrap();
+        assert_eq!(list.get_word_list().len(), 3);
+        assert_eq!(
+            list.explain("boxes"),
+            "boxes is ruled out by bored: the feedback would be 3, not 2"
+        );
+        assert_eq!(list.feedback_distribution("boxes"), vec![(String::from("1"), 3)]);
+
+        // anagrams always get the same count, so the best guess can only
+        // split off one word
+        let result = list.suggest(1);
+        assert_eq!(result[0].word, "react");
+        assert_eq!(result[0].worst_case, 2);
+
+        // color feedback is not valid in this mode
+        assert!(list.apply_guess("crane", "bygbb").is_err());
+        assert_eq!(list.get_guesses().len(), 1);
+    }
+
+    #[test]
+    fn feedback_mode_test_1() {
+        assert_eq!("jotto".parse(), Ok(FeedbackMode::Count));
+        assert_eq!("colors".parse(), Ok(FeedbackMode::Colors));
+        assert_eq!("mastermind".parse(), Ok(FeedbackMode::Pegs));
+        assert!("dice".parse::<FeedbackMode>().is_err());
+        assert_eq!(FeedbackMode::Count.to_string(), "count");
+        assert_eq!(common_letters("eerie", "there"), 3);
+    }
+
+    #[test]
+    fn pegs_mode_test_1() {
+        let mut list = WordleWords::new(generate_codes("123456", 4, true).unwrap());
+        assert_eq!(list.get_word_list().len(), 1296);
+        list.set_feedback_mode(FeedbackMode::Pegs);
+        assert!(FeedbackMode::Pegs.is_valid("1122", "0b0w"));
+        assert!(!FeedbackMode::Pegs.is_valid("1122", "3b2w"));
+        assert!(!FeedbackMode::Pegs.is_valid("1122", "3b"));
+
+        list.apply_guess("1122", "0b0w").unwrap();
+        assert_eq!(list.get_word_list().len(), 256);
+        list.apply_guess("3344", "4b0w").unwrap();
+        assert_eq!(list.get_word_list(), &vec![String::from("3344")]);
+        assert_eq!(
+            list.explain("3345"),
+            "3345 is ruled out by 3344: the feedback would be 3b0w, not 4b0w"
+        );
+    }
+
+    #[test]
+    fn generate_codes_test_1() {
+        // Bulls and Cows: four different digits
+        let codes = generate_codes("0123456789", 4, false).unwrap();
+        assert_eq!(codes.len(), 5040);
+        assert_eq!(codes[0], "0123");
+        assert_eq!(codes[5039], "9876");
+    }
+
+    #[test]
+    fn generate_codes_test_2() {
+        // repeated symbols are only used once
+        assert_eq!(generate_codes("1123", 1, true).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(generate_codes("1123", 3, false).unwrap().len(), 6);
+        // no codes of no symbols
+        assert!(generate_codes("12", 0, true).is_err());
+        // too many codes
+        assert!(generate_codes("0123456789", 10, true).is_err());
+        assert!(generate_codes("0123456789", 10, false).is_err());
+        assert_eq!(generate_codes("0123", 5, false).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn query_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crane"),
+            String::from("bored"),
+            String::from("boxes"),
+        ];
+        let mut list = WordleWords::new(v);
+        list.apply_query("[^c]???? +e =abcdeorstx").unwrap();
+        assert_eq!(
+            list.get_word_list(),
+            &vec![
+                String::from("react"),
+                String::from("trace"),
+                String::from("bored"),
+                String::from("boxes"),
+            ]
+        );
+        list.apply_query("b.[xr]?? -d").unwrap();
+        assert_eq!(list.get_word_list(), &vec![String::from("boxes")]);
+
+        // the pattern has to be as long as the word
+        assert!(!Query::parse("????").unwrap().matches("boxes"));
+        assert!(list.apply_query("bo*es").is_err());
+        assert_eq!(list.get_word_list().len(), 1);
+    }
+
+    #[test]
+    fn anagram_test_1() {
+        let v = vec![
+            String::from("react"),
+            String::from("trace"),
+            String::from("crate"),
+            String::from("crane"),
+            String::from("treat"),
+        ];
+        let mut list = WordleWords::new(v);
+        // 't' first and 'a' in the middle, and a 'c' that is not first
+        list.remove_letters("n");
+        list.correct_letters("t.a..").unwrap();
+        list.incorrect_letters("c....").unwrap();
+        list.anagram_letters("tacer");
+        assert_eq!(list.get_word_list(), &vec![String::from("trace")]);
+
+        list.reset_list();
+        list.only_letters("aerttc", false);
+        assert_eq!(list.get_word_list().len(), 4);
+        list.only_letters("aert", true);
+        assert_eq!(list.get_word_list(), &vec![String::from("treat")]);
+        assert!(!is_anagram("trace", "trac"));
+
+        // letters are read the same in any case
+        list.reset_list();
+        list.anagram_letters("TACER");
+        assert_eq!(list.get_word_list().len(), 3);
+        list.only_letters("ACERT", false);
+        assert_eq!(list.get_word_list().len(), 3);
+    }
 }
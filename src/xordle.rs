@@ -0,0 +1,404 @@
+// Xordle: two secret words, that share no letters, are played on the same
+// board. The feedback to a guess combines the feedback for both words, so
+// the solver keeps pairs of words rather than single words.
+
+use std::collections::HashSet;
+
+use crate::wordle::{self, Suggestion, WordleWords};
+
+// The number of possible words, and of other guesses, that suggest()
+// scores against the pairs.
+const SHORTLIST: usize = 100;
+
+/// Pairs of words, sharing no letters, that could be the two secret words
+/// of a Xordle game.
+///
+/// # Example
+/// ```
+/// use wordlesolver::wordle::WordleWords;
+/// use wordlesolver::xordle::Xordle;
+///
+/// let v = vec![
+///     String::from("crane"),
+///     String::from("bumpy"),
+///     String::from("dolts"),
+///     String::from("lumpy"),
+/// ];
+/// let mut game = Xordle::new(&WordleWords::new(v));
+/// assert_eq!(game.get_pairs().len(), 4);
+/// game.apply_guess("lucky", "bgybg").unwrap();
+/// assert_eq!(game.get_pairs(), vec![(String::from("crane"), String::from("bumpy"))]);
+/// ```
+pub struct Xordle {
+    // words that can be suggested as guesses
+    guess_list: Vec<String>,
+    // words that are in at least one of the pairs
+    candidates: Vec<String>,
+    // pairs of words, as indexes into candidates, first index lower
+    pairs: Vec<(usize, usize)>,
+    // guesses (and their feedback) that have been applied
+    guesses: Vec<(String, String)>,
+}
+
+/// Returns the Xordle feedback for a guess against two answers: each
+/// letter is green if it is green for either answer, yellow if it is
+/// yellow for either answer, and black otherwise.
+///
+/// # Example
+/// ```
+/// use wordlesolver::xordle::combined_feedback;
+///
+/// assert_eq!(combined_feedback("cramp", "crane", "bumpy"), "gggyy");
+/// ```
+pub fn combined_feedback(guess: &str, first: &str, second: &str) -> String {
+    let first = wordle::get_feedback(guess, first);
+    let second = wordle::get_feedback(guess, second);
+    first
+        .chars()
+        .zip(second.chars())
+        .map(|(a, b)| if rank(a as u8) >= rank(b as u8) { a } else { b })
+        .collect()
+}
+
+// Returns how much a feedback letter tells: black, then yellow, then
+// green.
+fn rank(color: u8) -> u8 {
+    match color {
+        b'g' => 2,
+        b'y' => 1,
+        _ => 0,
+    }
+}
+
+// Returns the letters 'a' to 'z' of a word as bits, to check if two words
+// share any letters.
+fn letter_mask(word: &str) -> u32 {
+    word.bytes()
+        .filter(|c| c.is_ascii_lowercase())
+        .fold(0, |mask, c| mask | 1 << (c - b'a'))
+}
+
+impl Xordle {
+    /// Returns every pair of the possible words of words that share no
+    /// letters. Any word of its original list can be guessed, but banned
+    /// words are not suggested.
+    pub fn new(words: &WordleWords) -> Xordle {
+        let candidates = words.get_word_list().clone();
+        let masks: Vec<u32> = candidates.iter().map(|w| letter_mask(w)).collect();
+        let mut pairs = Vec::new();
+        for i in 0..candidates.len() {
+            for j in i + 1..candidates.len() {
+                if masks[i] & masks[j] == 0 {
+                    pairs.push((i, j));
+                }
+            }
+        }
+
+        let mut xordle = Xordle {
+            guess_list: words
+                .get_original_list()
+                .iter()
+                .filter(|word| !words.is_banned(word))
+                .cloned()
+                .collect(),
+            candidates,
+            pairs,
+            guesses: Vec::new(),
+        };
+        xordle.keep_paired();
+        xordle
+    }
+
+    /// Keeps the pairs that would give the feedback to the guess.
+    ///
+    /// Returns an error, and keeps every pair, if the feedback is not
+    /// valid for the guess.
+    pub fn apply_guess(&mut self, guess: &str, feedback: &str) -> Result<(), String> {
+        if !wordle::is_valid_feedback(guess, feedback) {
+            return Err(format!(
+                "feedback {} is not valid for guess {}",
+                feedback, guess
+            ));
+        }
+        let feedback = feedback.as_bytes();
+
+        // feedback of each word on its own, and whether it can be one of
+        // the pair: no letter can tell more than the combined feedback
+        let scores = self.scores(guess);
+        let fits: Vec<bool> = scores
+            .iter()
+            .map(|score| {
+                score
+                    .iter()
+                    .zip(feedback)
+                    .all(|(&s, &f)| rank(s) <= rank(f))
+            })
+            .collect();
+
+        self.pairs.retain(|&(i, j)| {
+            fits[i]
+                && fits[j]
+                && (0..feedback.len())
+                    .all(|k| rank(scores[i][k]).max(rank(scores[j][k])) == rank(feedback[k]))
+        });
+        self.guesses.push((
+            guess.to_string(),
+            String::from_utf8(feedback.to_vec()).unwrap(),
+        ));
+        self.keep_paired();
+        Ok(())
+    }
+
+    // Returns the feedback of the guess against each candidate.
+    fn scores(&self, guess: &str) -> Vec<Vec<u8>> {
+        self.candidates
+            .iter()
+            .map(|word| {
+                let mut score = vec![b'b'; guess.len()];
+                wordle::score(guess.as_bytes(), word.as_bytes(), &mut score);
+                score
+            })
+            .collect()
+    }
+
+    // Returns the guesses worth scoring against the pairs: the SHORTLIST
+    // possible words, then the SHORTLIST other guesses, with the most
+    // information against the possible words on their own.
+    fn shortlist(&self, possible: &HashSet<&str>) -> Vec<&String> {
+        let total = self.candidates.len() as f64;
+        let mut words: Vec<(f64, &String)> = Vec::new();
+        let mut others: Vec<(f64, &String)> = Vec::new();
+        for guess in &self.guess_list {
+            let count = match wordle::feedback_count(guess.len()) {
+                Some(count) => count,
+                None => continue,
+            };
+            let mut groups = vec![0; count];
+            let mut feedback = vec![b'b'; guess.len()];
+            for word in &self.candidates {
+                wordle::score(guess.as_bytes(), word.as_bytes(), &mut feedback);
+                groups[wordle::feedback_index(&feedback)] += 1;
+            }
+            let information = wordle::information(&groups, total);
+            if possible.contains(guess.as_str()) {
+                words.push((information, guess));
+            } else {
+                others.push((information, guess));
+            }
+        }
+        for list in [&mut words, &mut others] {
+            list.sort_by(|a, b| b.0.total_cmp(&a.0));
+            list.truncate(SHORTLIST);
+        }
+
+        words
+            .into_iter()
+            .chain(others)
+            .map(|(_, guess)| guess)
+            .collect()
+    }
+
+    // Removes the candidates that are not in any pair, and renumbers the
+    // pairs.
+    fn keep_paired(&mut self) {
+        let mut used = vec![false; self.candidates.len()];
+        for &(i, j) in &self.pairs {
+            used[i] = true;
+            used[j] = true;
+        }
+        let mut index = vec![0; self.candidates.len()];
+        let mut candidates = Vec::new();
+        for (i, word) in self.candidates.iter().enumerate() {
+            if used[i] {
+                index[i] = candidates.len();
+                candidates.push(word.clone());
+            }
+        }
+        for pair in self.pairs.iter_mut() {
+            *pair = (index[pair.0], index[pair.1]);
+        }
+        self.candidates = candidates;
+    }
+
+    /// Returns the guesses, and their feedback, that have been applied.
+    pub fn get_guesses(&self) -> &Vec<(String, String)> {
+        &self.guesses
+    }
+
+    /// Returns the words that are in at least one of the possible pairs.
+    pub fn get_word_list(&self) -> &Vec<String> {
+        &self.candidates
+    }
+
+    /// Returns the possible pairs of secret words.
+    pub fn get_pairs(&self) -> Vec<(String, String)> {
+        self.pairs
+            .iter()
+            .map(|&(i, j)| (self.candidates[i].clone(), self.candidates[j].clone()))
+            .collect()
+    }
+
+    /// Returns up to count suggestions for the next guess, best first,
+    /// scored by the expected information of the combined feedback
+    /// against the possible pairs. possible is true for words in at least
+    /// one pair.
+    ///
+    /// Scoring a guess goes through every pair, so only the 100 possible
+    /// words and the 100 other guesses that best split the possible words
+    /// on their own are scored, and scoring stops once count possible
+    /// words split every pair. A better guess can be missed.
+    pub fn suggest(&self, count: usize) -> Vec<Suggestion> {
+        let total = self.pairs.len() as f64;
+        let mut suggestions: Vec<Suggestion> = Vec::new();
+        if self.pairs.is_empty() || count == 0 {
+            return suggestions;
+        }
+
+        let possible: HashSet<&str> = self.candidates.iter().map(|w| w.as_str()).collect();
+        let mut splitting = 0;
+        for guess in self.shortlist(&possible) {
+            // too long to group the feedbacks of
+            let count = match wordle::feedback_count(guess.len()) {
+                Some(count) => count,
+                None => continue,
+            };
+            let scores = self.scores(guess);
+            let mut groups = vec![0; count];
+            let mut feedback = vec![b'b'; guess.len()];
+            for &(i, j) in &self.pairs {
+                for k in 0..feedback.len() {
+                    feedback[k] = if rank(scores[i][k]) >= rank(scores[j][k]) {
+                        scores[i][k]
+                    } else {
+                        scores[j][k]
+                    };
+                }
+                groups[wordle::feedback_index(&feedback)] += 1;
+            }
+            let worst_case = groups.iter().copied().max().unwrap_or(0);
+            suggestions.push(Suggestion {
+                word: guess.clone(),
+                information: wordle::information(&groups, total),
+                worst_case,
+                possible: possible.contains(guess.as_str()),
+            });
+            // the possible words come first, and no guess does better
+            // than a possible word that splits every pair
+            if worst_case == 1 && possible.contains(guess.as_str()) {
+                splitting += 1;
+                if splitting == count {
+                    break;
+                }
+            }
+        }
+
+        suggestions.sort_by(|a, b| {
+            b.information
+                .total_cmp(&a.information)
+                .then(b.possible.cmp(&a.possible))
+        });
+        suggestions.truncate(count);
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_guess_test_1() {
+        let v = vec![
+            String::from("crane"),
+            String::from("bumpy"),
+            String::from("dolts"),
+            String::from("fight"),
+            String::from("bravo"),
+            String::from("crank"),
+            String::from("lumpy"),
+            String::from("bleak"),
+        ];
+        let mut game = Xordle::new(&WordleWords::new(v));
+        // "dolts" and "fight" share a 't', and "bravo" shares letters with
+        // every word but "fight" and "lumpy"
+        assert!(!game
+            .get_pairs()
+            .contains(&(String::from("dolts"), String::from("fight"))));
+        assert!(game
+            .get_pairs()
+            .contains(&(String::from("fight"), String::from("bravo"))));
+
+        game.apply_guess("cramp", "gggyy").unwrap();
+        assert_eq!(
+            game.get_pairs(),
+            vec![
+                (String::from("crane"), String::from("bumpy")),
+                (String::from("crane"), String::from("lumpy")),
+                (String::from("bumpy"), String::from("crank")),
+                (String::from("crank"), String::from("lumpy")),
+            ]
+        );
+        assert_eq!(game.get_word_list().len(), 4);
+
+        assert!(game.apply_guess("cramp", "gggy").is_err());
+        assert_eq!(game.get_guesses().len(), 1);
+    }
+
+    #[test]
+    fn suggest_test_1() {
+        let v = vec![
+            String::from("crane"),
+            String::from("bumpy"),
+            String::from("dolts"),
+            String::from("fight"),
+            String::from("bravo"),
+            String::from("crank"),
+            String::from("lumpy"),
+            String::from("bleak"),
+        ];
+        let mut game = Xordle::new(&WordleWords::new(v));
+        game.apply_guess("cramp", "gggyy").unwrap();
+        // "bleak" tells "bumpy" from "lumpy", and "crane" from "crank"
+        let result = game.suggest(1);
+        assert_eq!(result[0].word, "bleak");
+        assert_eq!(result[0].worst_case, 1);
+        assert_eq!(result[0].information, 2.0);
+    }
+
+    #[test]
+    fn suggest_test_2() {
+        let mut v = vec![
+            String::from("crane"),
+            String::from("bumpy"),
+            String::from("dolts"),
+            String::from("fight"),
+            String::from("bravo"),
+            String::from("crank"),
+            String::from("lumpy"),
+            String::from("bleak"),
+        ];
+        let others: Vec<String> = (0..150).map(|i| format!("z{:04}", i)).collect();
+        v.extend(others.iter().cloned());
+        let mut words = WordleWords::new(v);
+        words.remove_words(&others);
+        let game = Xordle::new(&words);
+
+        // every possible word, then only 100 other guesses
+        let possible: HashSet<&str> = game.candidates.iter().map(|w| w.as_str()).collect();
+        let shortlist = game.shortlist(&possible);
+        assert_eq!(shortlist.len(), game.candidates.len() + SHORTLIST);
+        assert!(shortlist[..game.candidates.len()]
+            .iter()
+            .all(|w| possible.contains(w.as_str())));
+
+        let mut game = Xordle::new(&words);
+        game.apply_guess("cramp", "gggyy").unwrap();
+        assert_eq!(game.suggest(1)[0].word, "bleak");
+    }
+
+    #[test]
+    fn combined_feedback_test_1() {
+        assert_eq!(combined_feedback("lucky", "crane", "bumpy"), "bgybg");
+        assert_eq!(combined_feedback("crane", "crane", "bumpy"), "ggggg");
+    }
+}
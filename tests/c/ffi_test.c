@@ -0,0 +1,57 @@
+/* Exercises the C interface, run by tests/ffi.rs. Exits with a non zero
+ * status if a check fails. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "wordlesolver.h"
+
+#define CHECK(condition)                                                     \
+    do {                                                                     \
+        if (!(condition)) {                                                  \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+                    #condition);                                             \
+            return 1;                                                        \
+        }                                                                    \
+    } while (0)
+
+int main(void) {
+    const char *words[] = {"react", "trace", "crane"};
+    WordleSolver *solver = wordle_solver_new(words, 3);
+    CHECK(solver != NULL);
+    CHECK(wordle_solver_candidate_count(solver) == 3);
+
+    CHECK(wordle_solver_apply_guess(solver, "crane", "yygby") == 0);
+    CHECK(wordle_solver_candidate_count(solver) == 1);
+    CHECK(strcmp(wordle_solver_candidate(solver, 0), "react") == 0);
+    CHECK(wordle_solver_candidate(solver, 1) == NULL);
+
+    /* invalid feedback is rejected */
+    CHECK(wordle_solver_apply_guess(solver, "crane", "yyg") == -1);
+
+    WordleSuggestions *suggestions = wordle_solver_suggest(solver, 2);
+    CHECK(suggestions != NULL);
+    CHECK(wordle_suggestions_count(suggestions) == 2);
+    CHECK(strcmp(wordle_suggestions_word(suggestions, 0), "react") == 0);
+    CHECK(wordle_suggestions_possible(suggestions, 0));
+    CHECK(wordle_suggestions_information(suggestions, 0) == 0.0);
+    CHECK(wordle_suggestions_worst_case(suggestions, 0) == 1);
+    wordle_suggestions_free(suggestions);
+
+    CHECK(wordle_solver_undo(solver) == 0);
+    CHECK(wordle_solver_candidate_count(solver) == 3);
+    CHECK(wordle_solver_undo(solver) == -1);
+
+    wordle_solver_set_hard_mode(solver, true);
+    CHECK(wordle_solver_apply_guess(solver, "crane", "gggbg") == 0);
+    /* 'trace' does not keep the green 'c' */
+    CHECK(wordle_solver_apply_guess(solver, "trace", "ggggg") == -1);
+    wordle_solver_reset(solver);
+    CHECK(wordle_solver_candidate_count(solver) == 3);
+
+    wordle_solver_free(solver);
+    wordle_solver_free(NULL);
+
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,126 @@
+// Tests for the C interface: the header has to declare every exported
+// function, with the same signature and nothing else, and
+// tests/c/ffi_test.c has to compile and run against the library.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+// directory holding the library built by cargo test (target/debug/deps,
+// where the test runs from: cargo build also copies it to target/debug,
+// but cargo test does not)
+fn target_dir() -> PathBuf {
+    let mut dir = env::current_exe().unwrap();
+    dir.pop();
+    dir
+}
+
+// Returns the C type of a Rust type used by the C interface, without
+// spaces, e.g. "constchar*" for "*const c_char".
+fn c_type(rust: &str) -> String {
+    let rust = rust.trim();
+    if let Some(inner) = rust.strip_prefix("*mut ") {
+        format!("{}*", c_type(inner))
+    } else if let Some(inner) = rust.strip_prefix("*const ") {
+        // a const pointer to a pointer is written after the pointer
+        if inner.trim().starts_with('*') {
+            format!("{}const*", c_type(inner))
+        } else {
+            format!("const{}*", c_type(inner))
+        }
+    } else {
+        match rust {
+            "c_char" => String::from("char"),
+            "c_int" => String::from("int"),
+            "usize" => String::from("size_t"),
+            "f64" => String::from("double"),
+            other => other.to_string(),
+        }
+    }
+}
+
+// Returns the C declarations of the functions of src/ffi.rs, without
+// spaces, e.g. "intwordle_solver_undo(WordleSolver*solver);".
+fn c_declarations(source: &str) -> Vec<String> {
+    let mut declarations = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find("pub unsafe extern \"C\" fn ") {
+        rest = &rest[start + "pub unsafe extern \"C\" fn ".len()..];
+        let signature = &rest[..rest.find('{').unwrap()];
+        let (name, signature) = signature.split_once('(').unwrap();
+        let (params, result) = signature.rsplit_once(')').unwrap();
+        let params: Vec<String> = params
+            .split(',')
+            .filter(|param| !param.trim().is_empty())
+            .map(|param| {
+                let (name, rust) = param.split_once(':').unwrap();
+                format!("{}{}", c_type(rust), name.trim())
+            })
+            .collect();
+        let result = match result.trim().strip_prefix("->") {
+            Some(rust) => c_type(rust),
+            None => String::from("void"),
+        };
+        declarations.push(format!("{}{}({});", result, name.trim(), params.join(",")));
+    }
+    declarations
+}
+
+#[test]
+fn header_test() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let source = fs::read_to_string(root.join("src/ffi.rs")).unwrap();
+    let header = fs::read_to_string(root.join("include/wordlesolver.h")).unwrap();
+    let header: String = header.split_whitespace().collect();
+
+    let declarations = c_declarations(&source);
+    assert!(!declarations.is_empty());
+    for declaration in &declarations {
+        assert!(
+            header.contains(declaration.as_str()),
+            "{} is not declared in include/wordlesolver.h",
+            declaration
+        );
+    }
+    // and nothing else is declared
+    assert_eq!(header.matches(");").count(), declarations.len());
+}
+
+#[test]
+fn c_program_test() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target = target_dir();
+    let program = target.join("ffi_test");
+
+    let status = Command::new("cc")
+        .arg(root.join("tests/c/ffi_test.c"))
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg("-L")
+        .arg(&target)
+        .arg("-lwordlesolver")
+        .arg("-o")
+        .arg(&program)
+        .status();
+    match status {
+        Ok(status) => assert!(status.success(), "unable to compile tests/c/ffi_test.c"),
+        Err(e) => {
+            // no C compiler available, nothing to test
+            eprintln!("skipping c_program_test, unable to run cc: {}", e);
+            return;
+        }
+    }
+
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &target)
+        .env("DYLD_LIBRARY_PATH", &target)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "ffi_test failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}